@@ -1,25 +1,69 @@
 #[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    ensure, entry_point, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response,
-    StdResult,
+    ensure, to_json_binary, to_json_vec, Addr, Api, Attribute, BankMsg, Binary, Coin, Deps,
+    DepsMut, Empty, Env, Event, HexBinary, MessageInfo, Order, Reply, Response, StdResult, Storage,
+    SubMsg, SubMsgResult, WasmMsg,
 };
 use cw2::set_contract_version;
 use cw_asset::Asset;
+use cw_storage_plus::Bound;
+use drand_verify::{G2PubkeyRfc, Pubkey};
 use std::ops::Add;
 
 use crate::error::ContractError;
 use crate::msg::{
-    ConfigResponse, ContractBalanceResponse, ExecuteMsg, InstantiateMsg, QueryMsg,
-    ShootDeadlineResponse,
+    AttestationKeyResponse, AttestationPayload, BondResponse, ClaimableResponse,
+    ConfigChangeResponse, ConfigChangesResponse, ConfigResponse, ContractBalanceResponse,
+    DisputeConfigResponse, DrandConfigResponse, ExecuteMsg, FeesResponse, GoalVote,
+    GoalVotesResponse, InstantiateMsg, LedgerResponse, LivenessResponse, NoisCallback,
+    PauseResponse, PausesResponse, PendingOwnerResponse, ProxyExecuteMsg, QueryMsg,
+    RandomnessProxyResponse, RefereesResponse, ResultAttestation, RoleHolders, RolesResponse,
+    SeedCommitmentResponse, SettlementResponse, ShootDeadlineResponse, ShotResponse, SlashResponse,
+    SlashesResponse,
+};
+use crate::physics::{self, Kick, KickResult};
+use crate::randomness::{
+    commit_reveal_randomness, drand_shot_randomness, keeper_position, physics_shot_randomness,
+    roll_bps, sha256,
+};
+use crate::state::{
+    AttestationKey, BondConfig, Config, ConfigChange, ConfigUpdate, CooldownPolicy, DisputeConfig,
+    DrandConfig, FeeDestination, FeeRecipient, FeeSchedule, Ledger, Liveness, OperatorBond, Pause,
+    PauseTarget, PendingConfigChange, PendingOwner, PoolBucket, ResolutionMode, Role,
+    SeedCommitment, Settlement, ShotResolution, ShotSession, ShotStatus, SlashRecord,
+    ATTESTATION_KEY, BOND_CONFIG, BPS_DENOMINATOR, CLAIMABLE, COMMITMENT_COUNT, CONFIG,
+    CONFIG_CHANGE_COUNT, DISPUTE_CONFIG, DRAND_CONFIG, GOAL_VOTES, LAST_SHOT_MAPPER, LEDGER,
+    LIVENESS, MAIN_DENOM, NEXT_COMMITMENT, OPERATOR_BOND, PAUSES, PAYOUT_COUNT, PENDING_BURNS,
+    PENDING_CONFIG_CHANGES, PENDING_OWNER, PENDING_PAYOUTS, PENDING_SHOTS, RANDOMNESS_PROXY,
+    REFEREE_THRESHOLD, ROLES, SEED_COMMITMENTS, SETTLEMENTS, SHOOT_DEADLINE_MAPPER, SHOT_COUNT,
+    SHOT_SESSIONS, SLASHES, SLASH_COUNT,
 };
-use crate::state::{Config, CONFIG, MAIN_DENOM, SHOOT_DEADLINE_MAPPER};
 
 // version info for migration
 const CONTRACT_NAME: &str = "crates.io:longshot_jackpot";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 // constants
-const SHOOT_DURATION: u64 = 90; // 90 seconds
+const DEFAULT_SHOOT_DURATION: u64 = 90; // 90 seconds
+const MIN_SHOOT_DURATION: u64 = 10;
+const MAX_SHOOT_DURATION: u64 = 3600;
+const DEFAULT_EXPIRE_LIMIT: u32 = 30;
+const MAX_EXPIRE_LIMIT: u32 = 100;
+const MAX_FEE_RECIPIENTS: u32 = 10;
+const DEFAULT_GOAL_PROBABILITY_BPS: u16 = 1000; // 10%
+const DEFAULT_REFUND_GRACE_PERIOD: u64 = 24 * 3600; // 1 day
+const MAX_REFUND_GRACE_PERIOD: u64 = 30 * 24 * 3600; // 30 days
+const DEFAULT_LIVENESS_PERIOD: u64 = 3 * 24 * 3600; // 3 days
+const MAX_DISPUTE_PERIOD: u64 = 30 * 24 * 3600; // 30 days
+const MAX_CLIENT_SEED_LEN: u32 = 64;
+const DEFAULT_UNBONDING_PERIOD: u64 = 7 * 24 * 3600; // 7 days
+const MAX_UNBONDING_PERIOD: u64 = 30 * 24 * 3600; // 30 days
+const DEFAULT_SLASH_BPS: u16 = 1000; // 10%
+const DEFAULT_SLASH_LIMIT: u32 = 30;
+const MAX_SLASH_LIMIT: u32 = 100;
+const MAX_CONFIG_TIMELOCK: u64 = 30 * 24 * 3600; // 30 days
+const CONFIG_CHANGE_GRACE_PERIOD: u64 = 7 * 24 * 3600; // 7 days
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -35,15 +79,65 @@ pub fn instantiate(
         .and_then(|addr_string| deps.api.addr_validate(addr_string.as_str()).ok())
         .unwrap_or(info.sender);
 
-    let config = Config {
-        owner: owner.clone(),
+    // The settings shared with `UpdateConfig` are validated the same way
+    let update = ConfigUpdate {
+        ticket_price: msg.ticket_price,
+        fee_schedule: msg.fee_schedule,
+        fee_recipients: msg.fee_recipients,
+        shoot_duration: msg.shoot_duration,
+        cooldown_policy: msg.cooldown_policy,
+        resolution_mode: msg.resolution_mode,
+        goal_probability_bps: msg.goal_probability_bps,
+        refund_grace_period: msg.refund_grace_period,
+        liveness_period: msg.liveness_period,
+        config_timelock: msg.config_timelock,
+    };
+    validate_config_update(deps.api, &update)?;
+
+    let mut config = Config {
+        owner: Some(owner.clone()),
         ticket_price: 0,
-        reward_percentage: 80,
-        admin_percentage: 4,
-        shoot_duration: 90,
+        fee_schedule: FeeSchedule {
+            reward_bps: 8000,
+            admin_bps: 400,
+            reserve_bps: 0,
+            ticket_fee_bps: 0,
+        },
+        fee_recipients: vec![FeeRecipient {
+            destination: FeeDestination::Address(owner.clone()),
+            weight_bps: BPS_DENOMINATOR as u16,
+        }],
+        shoot_duration: DEFAULT_SHOOT_DURATION,
+        cooldown_policy: CooldownPolicy::AllowOverlap,
+        resolution_mode: ResolutionMode::Referee,
+        goal_probability_bps: DEFAULT_GOAL_PROBABILITY_BPS,
+        refund_grace_period: DEFAULT_REFUND_GRACE_PERIOD,
+        liveness_period: DEFAULT_LIVENESS_PERIOD,
+        config_timelock: 0,
     };
+    update.apply(&mut config);
 
     CONFIG.save(deps.storage, &config)?;
+    SHOT_COUNT.save(deps.storage, &0)?;
+    CONFIG_CHANGE_COUNT.save(deps.storage, &0)?;
+    COMMITMENT_COUNT.save(deps.storage, &0)?;
+    NEXT_COMMITMENT.save(deps.storage, &1)?;
+    REFEREE_THRESHOLD.save(deps.storage, &0)?;
+    OPERATOR_BOND.save(deps.storage, &OperatorBond::default())?;
+    BOND_CONFIG.save(
+        deps.storage,
+        &BondConfig {
+            unbonding_period: DEFAULT_UNBONDING_PERIOD,
+            slash_bps: DEFAULT_SLASH_BPS,
+        },
+    )?;
+    SLASH_COUNT.save(deps.storage, &0)?;
+    LIVENESS.save(deps.storage, &Liveness::default())?;
+    LEDGER.save(deps.storage, &Ledger::default())?;
+    for grant in msg.roles.unwrap_or_default() {
+        let address = deps.api.addr_validate(&grant.address)?;
+        ROLES.save(deps.storage, (grant.role.as_str(), &address), &Empty {})?;
+    }
 
     // main denom, depends on the chain
     // "uosmo" for osmosis,
@@ -66,52 +160,182 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::SetTicketPrice { new_ticket_price } => {
-            execute_set_ticket_price(deps, info, new_ticket_price)
+        ExecuteMsg::UpdateConfig {
+            ticket_price,
+            fee_schedule,
+            fee_recipients,
+            shoot_duration,
+            cooldown_policy,
+            resolution_mode,
+            goal_probability_bps,
+            refund_grace_period,
+            liveness_period,
+            config_timelock,
+        } => execute_update_config(
+            deps,
+            info,
+            env,
+            ConfigUpdate {
+                ticket_price,
+                fee_schedule,
+                fee_recipients,
+                shoot_duration,
+                cooldown_policy,
+                resolution_mode,
+                goal_probability_bps,
+                refund_grace_period,
+                liveness_period,
+                config_timelock,
+            },
+        ),
+        ExecuteMsg::Shoot { client_seed, kick } => {
+            execute_shoot(deps, info, env, client_seed, kick)
+        }
+        ExecuteMsg::GoalShot {
+            shot_id,
+            attestation,
+        } => execute_goal_shot(deps, info, env, shot_id, attestation),
+        ExecuteMsg::MissShot {
+            shot_id,
+            attestation,
+        } => execute_miss_shot(deps, info, env, shot_id, attestation),
+        ExecuteMsg::ExpireShots { limit } => execute_expire_shots(deps, env, limit),
+        ExecuteMsg::SyncBalance { bucket } => execute_sync_balance(deps, info, env, bucket),
+        ExecuteMsg::Claim {} => execute_claim(deps, info),
+        ExecuteMsg::WithdrawFees { amount, recipient } => {
+            execute_withdraw_fees(deps, info, env, amount, recipient)
+        }
+        ExecuteMsg::ReleaseReserve { amount, recipient } => {
+            execute_release_reserve(deps, info, env, amount, recipient)
+        }
+        ExecuteMsg::CommitSeeds { commitments } => execute_commit_seeds(deps, info, commitments),
+        ExecuteMsg::RevealShot {
+            shot_id,
+            server_seed,
+            signature,
+        } => execute_reveal_shot(deps, env, shot_id, server_seed, signature),
+        ExecuteMsg::ResolveDrandShot { shot_id, signature } => {
+            execute_resolve_drand_shot(deps, env, shot_id, signature)
+        }
+        ExecuteMsg::SetDrandConfig { new_drand_config } => {
+            execute_set_drand_config(deps, info, new_drand_config)
+        }
+        ExecuteMsg::NoisReceive { callback } => execute_nois_receive(deps, info, env, callback),
+        ExecuteMsg::SetRandomnessProxy {
+            new_randomness_proxy,
+        } => execute_set_randomness_proxy(deps, info, new_randomness_proxy),
+        ExecuteMsg::SetAttestationKey {
+            new_attestation_key,
+        } => execute_set_attestation_key(deps, info, new_attestation_key),
+        ExecuteMsg::VoteGoal { shot_id } => execute_vote_goal(deps, info, env, shot_id),
+        ExecuteMsg::GrantRole { address, role } => execute_grant_role(deps, info, address, role),
+        ExecuteMsg::RevokeRole { address, role } => execute_revoke_role(deps, info, address, role),
+        ExecuteMsg::SetRefereeThreshold { new_threshold } => {
+            execute_set_referee_threshold(deps, info, env, new_threshold)
+        }
+        ExecuteMsg::Dispute { shot_id } => execute_dispute(deps, info, env, shot_id),
+        ExecuteMsg::ResolveDispute { shot_id, uphold } => {
+            execute_resolve_dispute(deps, info, env, shot_id, uphold)
+        }
+        ExecuteMsg::Finalize { shot_id } => execute_finalize(deps, env, shot_id),
+        ExecuteMsg::SetDisputeConfig { new_dispute_config } => {
+            execute_set_dispute_config(deps, info, env, new_dispute_config)
+        }
+        ExecuteMsg::Bond {} => execute_bond(deps, info),
+        ExecuteMsg::Unbond { amount } => execute_unbond(deps, info, env, amount),
+        ExecuteMsg::WithdrawBond {} => execute_withdraw_bond(deps, info, env),
+        ExecuteMsg::SetBondConfig { new_bond_config } => {
+            execute_set_bond_config(deps, info, env, new_bond_config)
+        }
+        ExecuteMsg::RefundShot { shot_id } => execute_refund_shot(deps, info, env, shot_id),
+        ExecuteMsg::CheckLiveness {} => execute_check_liveness(deps, env),
+        ExecuteMsg::ResumeNormalMode {} => execute_resume_normal_mode(deps, info),
+        ExecuteMsg::ProposeNewOwner { address, expiry } => {
+            execute_propose_new_owner(deps, info, env, address, expiry)
+        }
+        ExecuteMsg::AcceptOwnership {} => execute_accept_ownership(deps, info, env),
+        ExecuteMsg::CancelOwnershipProposal {} => execute_cancel_ownership_proposal(deps, info),
+        ExecuteMsg::RenounceOwnership {} => execute_renounce_ownership(deps, info, env),
+        ExecuteMsg::Pause { what } => execute_pause(deps, info, env, what),
+        ExecuteMsg::Unpause { what, unpause_at } => {
+            execute_unpause(deps, info, env, what, unpause_at)
         }
-        ExecuteMsg::SetRewardPercentage {
-            new_reward_percentage,
-        } => execute_set_reward_percentage(deps, info, new_reward_percentage),
-        ExecuteMsg::SetAdminPercentage {
-            new_admin_percentage,
-        } => execute_set_admin_percentage(deps, info, new_admin_percentage),
-        ExecuteMsg::Shoot {} => execute_shoot(deps, info, env),
-        ExecuteMsg::GoalShot { player_address } => {
-            execute_goal_shot(deps, info, env, player_address)
+        ExecuteMsg::ApplyConfigChange { change_id } => {
+            execute_apply_config_change(deps, env, change_id)
         }
+        ExecuteMsg::CancelConfigChange { change_id } => {
+            execute_cancel_config_change(deps, info, change_id)
+        }
+    }
+}
+
+// Only the current owner passes, nobody does once ownership is renounced
+fn ensure_owner(storage: &dyn Storage, sender: &Addr) -> Result<Addr, ContractError> {
+    match CONFIG.load(storage)?.owner {
+        Some(owner) if owner == *sender => Ok(owner),
+        _ => Err(ContractError::Unauthorized {}),
+    }
+}
+
+fn ensure_not_paused(
+    storage: &dyn Storage,
+    env: &Env,
+    what: PauseTarget,
+) -> Result<(), ContractError> {
+    let pause = PAUSES.may_load(storage, what.as_str())?;
+    if pause.is_some_and(|pause| pause.is_active(env.block.time.seconds())) {
+        return Err(ContractError::Paused {
+            what: what.as_str().to_string(),
+        });
+    }
+    Ok(())
+}
+
+// Passes for holders of the role. The owner implicitly holds the admin role only, and
+// has to grant itself any other role like everyone else.
+fn ensure_role(storage: &dyn Storage, sender: &Addr, role: Role) -> Result<(), ContractError> {
+    let owner = CONFIG.load(storage)?.owner;
+    let implicit_admin = role == Role::Admin && owner.as_ref() == Some(sender);
+    if implicit_admin || ROLES.has(storage, (role.as_str(), sender)) {
+        return Ok(());
     }
+    Err(ContractError::MissingRole {
+        role: role.as_str().to_string(),
+    })
 }
 
 pub fn execute_shoot(
     deps: DepsMut,
     info: MessageInfo,
     env: Env,
+    client_seed: Option<String>,
+    kick: Option<Kick>,
 ) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.storage, &env, PauseTarget::Shooting)?;
     let player = info.sender;
+    let config = CONFIG.load(deps.storage)?;
+
+    // No new shots while the referee is not keeping up
+    let now = env.block.time.seconds();
+    let mut liveness = LIVENESS.load(deps.storage)?;
+    ensure!(
+        !liveness.is_degraded(now, config.liveness_period),
+        ContractError::DegradedMode {}
+    );
+    if liveness.awaiting_since.is_none() {
+        liveness.awaiting_since = Some(now);
+        LIVENESS.save(deps.storage, &liveness)?;
+    }
 
-    // === Uncomment this to let user wait til the deadline pass before reshoot ===
-    // Check if the player is already joined
-    // let may_shoot_deadline_player = SHOOT_DEADLINE_MAPPER.may_load(deps.storage, player.clone())?;
-    // match may_shoot_deadline_player {
-    //     Some(shoot_deadline_player) => {
-    //         if shoot_deadline_player != 0 {
-    //             //  Assert that the last shoot deadline is passed
-    //             if shoot_deadline_player > env.block.time.seconds() {
-    //                 return Err(ContractError::ShootDeadlineNotPassed {});
-    //             }
-    //         }
-    //     }
-    //     None => {}
-    // }
-    // ============================================================================
+    // Check if the player is allowed to shoot again
+    assert_cooldown_passed(deps.as_ref(), &config.cooldown_policy, &player, &env)?;
 
     // Check if the player has enough funds to shoot
     ensure!(!info.funds.is_empty(), ContractError::InvalidFund {});
-    let cur_ticket_price = CONFIG.load(deps.storage)?.ticket_price;
+    let cur_ticket_price = config.ticket_price;
     let main_denom = MAIN_DENOM.load(deps.storage)?;
     ensure!(
-        info.funds[0].denom == main_denom.to_string()
-            && info.funds[0].amount.u128() == cur_ticket_price,
+        info.funds[0].denom == main_denom && info.funds[0].amount.u128() == cur_ticket_price,
         ContractError::InvalidPriceIndex0 {
             expected_denom: main_denom.to_string(),
             expected_amount: cur_ticket_price,
@@ -120,554 +344,6601 @@ pub fn execute_shoot(
         }
     );
 
+    // Take the operator fee from the ticket, credit the rest to the jackpot
+    // and lock the jackpot the player is playing for
+    let mut ledger = LEDGER.load(deps.storage)?;
+    let ticket_fee = split_with_dust(
+        cur_ticket_price,
+        config.fee_schedule.ticket_fee_bps,
+        &mut ledger.admin_dust,
+    );
+    ledger.admin_fees += ticket_fee;
+    ledger.fees_accrued += ticket_fee;
+    ledger.jackpot += cur_ticket_price - ticket_fee;
+    let jackpot_basis = ledger.jackpot;
+
+    // Bind the new shot to the data its resolution mode needs, the basis is only
+    // ever rebased down so this is the most the shot can win
+    let shot_id = SHOT_COUNT.load(deps.storage)? + 1;
+    let prize = jackpot_basis * config.fee_schedule.reward_bps as u128 / BPS_DENOMINATOR;
+    let resolution = bind_resolution(
+        deps.storage,
+        &env,
+        &config,
+        shot_id,
+        client_seed,
+        kick,
+        prize,
+    )?;
+    LEDGER.save(deps.storage, &ledger)?;
+
     // Set the shoot deadline for the player
     let cur_timestamp = env.block.time.seconds();
-    let shoot_deadline = cur_timestamp.add(SHOOT_DURATION);
+    let shoot_deadline = cur_timestamp.add(config.shoot_duration);
     SHOOT_DEADLINE_MAPPER.save(deps.storage, player.clone(), &shoot_deadline)?;
 
-    Ok(Response::new()
+    // Open a new shot session, which can be resolved only once
+    SHOT_COUNT.save(deps.storage, &shot_id)?;
+    let session = ShotSession {
+        id: shot_id,
+        player: player.clone(),
+        paid_amount: cur_ticket_price,
+        started_at: cur_timestamp,
+        duration: config.shoot_duration,
+        deadline: shoot_deadline,
+        status: ShotStatus::Pending,
+        ticket_fee,
+        jackpot_basis,
+        fee_schedule: config.fee_schedule,
+        resolution,
+    };
+    SHOT_SESSIONS.save(deps.storage, shot_id, &session)?;
+    PENDING_SHOTS.save(deps.storage, shot_id, &shoot_deadline)?;
+    LAST_SHOT_MAPPER.save(deps.storage, session.player.clone(), &shot_id)?;
+
+    let mut res = Response::new()
+        .add_event(shot_status_event(&session, &env))
         .add_attribute("method", "execute_shoot")
-        .add_attribute("shoot_deadline", shoot_deadline.to_string()))
+        .add_attribute("shot_id", shot_id.to_string())
+        .add_attribute("shoot_deadline", shoot_deadline.to_string())
+        .add_attribute("jackpot_basis", jackpot_basis.to_string());
+    if ticket_fee > 0 {
+        res = res.add_attribute("ticket_fee", ticket_fee.to_string());
+    }
+    // Ask the proxy for randomness, the shot is resolved when it calls back
+    if let ShotResolution::RandomnessProxy { job_id, .. } = &session.resolution {
+        let proxy = RANDOMNESS_PROXY.load(deps.storage)?;
+        res = res
+            .add_attribute("job_id", job_id)
+            .add_submessage(SubMsg::new(WasmMsg::Execute {
+                contract_addr: proxy.to_string(),
+                msg: to_json_binary(&ProxyExecuteMsg::GetNextRandomness {
+                    job_id: job_id.clone(),
+                })?,
+                funds: vec![],
+            }));
+    }
+    Ok(res)
+}
+
+fn bind_resolution(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    shot_id: u64,
+    client_seed: Option<String>,
+    kick: Option<Kick>,
+    prize: u128,
+) -> Result<ShotResolution, ContractError> {
+    match config.resolution_mode {
+        ResolutionMode::Referee => Ok(ShotResolution::Referee),
+        ResolutionMode::CommitReveal => {
+            let client_seed = client_seed
+                .filter(|seed| !seed.is_empty() && seed.len() as u32 <= MAX_CLIENT_SEED_LEN)
+                .ok_or(ContractError::InvalidClientSeed {
+                    max_len: MAX_CLIENT_SEED_LEN,
+                })?;
+
+            // The operator knows its next seed, a later beacon keeps it from grinding player seeds
+            let round = bind_drand_round(storage, env, config)?;
+            lock_prize_bond(storage, prize)?;
+            let commitment_id = bind_seed_commitment(storage, shot_id)?;
+            Ok(ShotResolution::CommitReveal {
+                commitment_id,
+                client_seed,
+                round,
+                goal_probability_bps: config.goal_probability_bps,
+                prize_bond: prize,
+            })
+        }
+        ResolutionMode::Physics => {
+            let kick = kick
+                .filter(|kick| kick.is_valid())
+                .ok_or(ContractError::InvalidKick {})?;
+            let round = bind_drand_round(storage, env, config)?;
+            lock_prize_bond(storage, prize)?;
+            let commitment_id = bind_seed_commitment(storage, shot_id)?;
+            Ok(ShotResolution::Physics {
+                commitment_id,
+                kick,
+                round,
+                goal_probability_bps: config.goal_probability_bps,
+                prize_bond: prize,
+            })
+        }
+        ResolutionMode::Drand => Ok(ShotResolution::Drand {
+            round: bind_drand_round(storage, env, config)?,
+            goal_probability_bps: config.goal_probability_bps,
+        }),
+        ResolutionMode::RefereeQuorum => {
+            let threshold = REFEREE_THRESHOLD.load(storage)?;
+            ensure!(threshold > 0, ContractError::RefereeQuorumNotConfigured {});
+            Ok(ShotResolution::RefereeQuorum { threshold })
+        }
+        ResolutionMode::RandomnessProxy => {
+            ensure!(
+                RANDOMNESS_PROXY.may_load(storage)?.is_some(),
+                ContractError::RandomnessProxyNotConfigured {}
+            );
+            Ok(ShotResolution::RandomnessProxy {
+                job_id: randomness_job_id(shot_id),
+                goal_probability_bps: config.goal_probability_bps,
+            })
+        }
+    }
+}
+
+// Bind to the first round nobody can know yet, it has to be out before the deadline
+fn bind_drand_round(
+    storage: &dyn Storage,
+    env: &Env,
+    config: &Config,
+) -> Result<u64, ContractError> {
+    let drand = DRAND_CONFIG
+        .may_load(storage)?
+        .ok_or(ContractError::DrandNotConfigured {})?;
+    let now = env.block.time.seconds();
+    let round = drand.next_round(now);
+    ensure!(
+        drand.round_time(round) < now + config.shoot_duration,
+        ContractError::DrandRoundTooLate { round }
+    );
+    Ok(round)
+}
+
+fn verify_drand_beacon(
+    storage: &dyn Storage,
+    round: u64,
+    signature: &HexBinary,
+) -> Result<(), ContractError> {
+    let drand = DRAND_CONFIG.load(storage)?;
+    let pubkey = G2PubkeyRfc::from_variable(drand.pubkey.as_slice()).map_err(|err| {
+        ContractError::InvalidDrandConfig {
+            reason: err.to_string(),
+        }
+    })?;
+    let verified = pubkey
+        .verify(round, b"", signature.as_slice())
+        .unwrap_or(false);
+    ensure!(verified, ContractError::InvalidDrandBeacon { round });
+    Ok(())
+}
+
+// Take the oldest unused operator commitment
+fn bind_seed_commitment(storage: &mut dyn Storage, shot_id: u64) -> Result<u64, ContractError> {
+    let commitment_id = NEXT_COMMITMENT.load(storage)?;
+    let mut commitment = SEED_COMMITMENTS
+        .may_load(storage, commitment_id)?
+        .ok_or(ContractError::NoSeedCommitment {})?;
+    commitment.shot_id = Some(shot_id);
+    SEED_COMMITMENTS.save(storage, commitment_id, &commitment)?;
+    NEXT_COMMITMENT.save(storage, &(commitment_id + 1))?;
+    Ok(commitment_id)
+}
+
+fn assert_resolution_mode(
+    session: &ShotSession,
+    mode: ResolutionMode,
+) -> Result<(), ContractError> {
+    let shot_mode = session.resolution.mode();
+    ensure!(
+        shot_mode == mode,
+        ContractError::WrongResolutionMode {
+            shot_id: session.id,
+            mode: shot_mode.as_str().to_string(),
+        }
+    );
+    Ok(())
+}
+
+// Check the player's previous shot against the configured re-shoot policy
+fn assert_cooldown_passed(
+    deps: Deps,
+    policy: &CooldownPolicy,
+    player: &Addr,
+    env: &Env,
+) -> Result<(), ContractError> {
+    let last_shot_id = match LAST_SHOT_MAPPER.may_load(deps.storage, player.clone())? {
+        Some(last_shot_id) => last_shot_id,
+        None => return Ok(()),
+    };
+    let last_shot = SHOT_SESSIONS.load(deps.storage, last_shot_id)?;
+    let available_at = match policy {
+        CooldownPolicy::AllowOverlap => return Ok(()),
+        CooldownPolicy::UntilResolved => {
+            if last_shot.status != ShotStatus::Pending {
+                return Ok(());
+            }
+            last_shot.deadline
+        }
+        CooldownPolicy::Fixed { seconds } => last_shot.started_at.saturating_add(*seconds),
+    };
+    ensure!(
+        env.block.time.seconds() >= available_at,
+        ContractError::ShootCooldownActive { available_at }
+    );
+    Ok(())
+}
+
+// Emitted on every status change of a shot, so the full lifecycle can be rebuilt from events
+fn shot_status_event(session: &ShotSession, env: &Env) -> Event {
+    Event::new("shot_status")
+        .add_attribute("shot_id", session.id.to_string())
+        .add_attribute("player", session.player.to_string())
+        .add_attribute("status", session.status.as_str())
+        .add_attribute("timestamp", env.block.time.seconds().to_string())
+}
+
+// Move a pending shot into a terminal status
+fn finalize_shot(
+    storage: &mut dyn Storage,
+    session: &mut ShotSession,
+    status: ShotStatus,
+) -> StdResult<()> {
+    session.status = status;
+    SHOT_SESSIONS.save(storage, session.id, session)?;
+    PENDING_SHOTS.remove(storage, session.id);
+    Ok(())
+}
+
+// Restart the liveness clock after a resolution, a missed period is recorded first
+fn record_resolution(storage: &mut dyn Storage, env: &Env) -> StdResult<()> {
+    let now = env.block.time.seconds();
+    let liveness_period = CONFIG.load(storage)?.liveness_period;
+    let mut liveness = LIVENESS.load(storage)?;
+    liveness.degraded = liveness.is_degraded(now, liveness_period);
+    let pending = PENDING_SHOTS
+        .keys(storage, None, None, Order::Ascending)
+        .next()
+        .is_some();
+    liveness.awaiting_since = if pending { Some(now) } else { None };
+    LIVENESS.save(storage, &liveness)
+}
+
+// Expired and refunded shots no longer wait on the referee. The clock restarts from the
+// oldest shot still pending, without counting as a resolution.
+fn release_liveness(storage: &mut dyn Storage) -> StdResult<()> {
+    let mut liveness = LIVENESS.load(storage)?;
+    let oldest = PENDING_SHOTS
+        .keys(storage, None, None, Order::Ascending)
+        .next()
+        .transpose()?;
+    liveness.awaiting_since = match oldest {
+        Some(shot_id) => {
+            let started_at = SHOT_SESSIONS.load(storage, shot_id)?.started_at;
+            Some(liveness.awaiting_since.unwrap_or_default().max(started_at))
+        }
+        None => None,
+    };
+    LIVENESS.save(storage, &liveness)
+}
+
+// Load a shot session and assert it has not been resolved yet
+fn load_pending_shot(storage: &dyn Storage, shot_id: u64) -> Result<ShotSession, ContractError> {
+    let session = SHOT_SESSIONS
+        .may_load(storage, shot_id)?
+        .ok_or(ContractError::ShotNotFound { shot_id })?;
+    if session.status != ShotStatus::Pending {
+        return Err(ContractError::ShotAlreadyResolved {
+            shot_id,
+            status: session.status.as_str().to_string(),
+        });
+    }
+    Ok(session)
 }
 
 pub fn execute_goal_shot(
     deps: DepsMut,
     info: MessageInfo,
     env: Env,
-    player_address: Addr,
+    shot_id: u64,
+    attestation: Option<ResultAttestation>,
 ) -> Result<Response, ContractError> {
-    let owner = CONFIG.load(deps.storage)?.owner;
-    if info.sender != owner {
-        return Err(ContractError::Unauthorized {});
+    ensure_not_paused(deps.storage, &env, PauseTarget::Resolutions)?;
+    // Assert that the shot is still open and the shoot deadline is not passed
+    let session = load_pending_shot(deps.storage, shot_id)?;
+    assert_referee_result(
+        deps.as_ref(),
+        &info,
+        &env,
+        &session,
+        ShotStatus::Goal,
+        attestation,
+    )?;
+    if env.block.time.seconds() >= session.deadline {
+        return Err(ContractError::ShootDeadlinePassed {});
     }
 
-    // Assert that player joined the game
-    let may_shoot_deadline_player =
-        SHOOT_DEADLINE_MAPPER.may_load(deps.storage, player_address.clone())?;
-    match may_shoot_deadline_player {
-        Some(shoot_deadline_player) => {
-            // Assert that the shoot deadline is not passed
-            if env.block.time.seconds() >= shoot_deadline_player {
-                return Err(ContractError::ShootDeadlinePassed {});
-            }
-        }
+    let res = Response::new().add_attribute("method", "goal_shot");
+    settle_goal(deps, &env, session, res)
+}
+
+// A referee result comes from a referee, or from anyone holding a valid attestation
+fn assert_referee_result(
+    deps: Deps,
+    info: &MessageInfo,
+    env: &Env,
+    session: &ShotSession,
+    outcome: ShotStatus,
+    attestation: Option<ResultAttestation>,
+) -> Result<(), ContractError> {
+    assert_resolution_mode(session, ResolutionMode::Referee)?;
+    let attestation = match attestation {
+        Some(attestation) => attestation,
         None => {
-            return Err(ContractError::PlayerNotJoined {});
+            ensure_role(deps.storage, &info.sender, Role::Referee)?;
+            return Ok(());
+        }
+    };
+
+    let shot_id = session.id;
+    ensure!(
+        env.block.time.seconds() < attestation.expires_at,
+        ContractError::AttestationExpired { shot_id }
+    );
+    let key = ATTESTATION_KEY
+        .may_load(deps.storage)?
+        .ok_or(ContractError::AttestationKeyNotConfigured {})?;
+    let payload = AttestationPayload {
+        contract: env.contract.address.to_string(),
+        shot_id,
+        player: session.player.to_string(),
+        outcome,
+        expires_at: attestation.expires_at,
+    };
+    let hash = sha256(&to_json_vec(&payload)?);
+    let verified = match key {
+        AttestationKey::Secp256k1(pubkey) => {
+            deps.api
+                .secp256k1_verify(&hash, attestation.signature.as_slice(), pubkey.as_slice())
+        }
+        AttestationKey::Ed25519(pubkey) => {
+            deps.api
+                .ed25519_verify(&hash, attestation.signature.as_slice(), pubkey.as_slice())
         }
     }
+    .unwrap_or(false);
+    ensure!(verified, ContractError::InvalidAttestation { shot_id });
+    Ok(())
+}
 
-    // Get how much reward the player should get
-    let config = CONFIG.load(deps.storage)?;
-    let main_denom = MAIN_DENOM.load(deps.storage)?;
-    let reward_percentage = config.reward_percentage;
-    let admin_percentage = config.admin_percentage;
-    let contract_balance = deps
-        .querier
-        .query_balance(&env.contract.address, &main_denom.to_string())?
-        .amount
-        .u128();
-    let reward_amount = contract_balance * reward_percentage as u128 / 100;
-    let admin_amount = contract_balance * admin_percentage as u128 / 100;
+// A referee votes for a goal, the shot pays out once the quorum is reached
+pub fn execute_vote_goal(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    shot_id: u64,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.storage, &env, PauseTarget::Resolutions)?;
+    ensure_role(deps.storage, &info.sender, Role::Referee)?;
+
+    let session = load_pending_shot(deps.storage, shot_id)?;
+    let threshold = match session.resolution {
+        ShotResolution::RefereeQuorum { threshold } => threshold,
+        ref resolution => {
+            return Err(ContractError::WrongResolutionMode {
+                shot_id,
+                mode: resolution.mode().as_str().to_string(),
+            })
+        }
+    };
+    let now = env.block.time.seconds();
+    if now >= session.deadline {
+        return Err(ContractError::ShootDeadlinePassed {});
+    }
+    ensure!(
+        !GOAL_VOTES.has(deps.storage, (shot_id, &info.sender)),
+        ContractError::AlreadyVoted { shot_id }
+    );
+    GOAL_VOTES.save(deps.storage, (shot_id, &info.sender), &now)?;
+
+    // Votes of referees removed since then no longer count
+    let mut votes = 0u32;
+    for referee in GOAL_VOTES
+        .prefix(shot_id)
+        .keys(deps.storage, None, None, Order::Ascending)
+    {
+        if ROLES.has(deps.storage, (Role::Referee.as_str(), &referee?)) {
+            votes += 1;
+        }
+    }
 
-    // Init response
     let res = Response::new()
-        .add_attribute("method", "goal_shot")
-        .add_attribute("pre_balance", contract_balance.to_string());
+        .add_event(
+            Event::new("goal_vote")
+                .add_attribute("shot_id", shot_id.to_string())
+                .add_attribute("referee", info.sender.to_string()),
+        )
+        .add_attribute("method", "vote_goal")
+        .add_attribute("votes", votes.to_string())
+        .add_attribute("threshold", threshold.to_string());
+    if votes < threshold {
+        return Ok(res.add_attribute("shot_id", shot_id.to_string()));
+    }
+    settle_goal(deps, &env, session, res)
+}
+
+// Pay out a pending shot as a goal
+fn settle_goal(
+    mut deps: DepsMut,
+    env: &Env,
+    mut session: ShotSession,
+    res: Response,
+) -> Result<Response, ContractError> {
+    let shot_id = session.id;
+
+    // Take the prize locked at shoot time out of the jackpot, capped by what is left in it
+    let mut ledger = LEDGER.load(deps.storage)?;
+    let pre_jackpot = ledger.jackpot;
+    session.jackpot_basis = session.jackpot_basis.min(pre_jackpot);
+    let reward_amount = session.reward_amount();
+    let admin_amount = split_with_dust(
+        session.jackpot_basis,
+        session.fee_schedule.admin_bps,
+        &mut ledger.admin_dust,
+    )
+    .min(pre_jackpot - reward_amount);
+    let reserve_amount = split_with_dust(
+        session.jackpot_basis,
+        session.fee_schedule.reserve_bps,
+        &mut ledger.reserve_dust,
+    )
+    .min(pre_jackpot - reward_amount - admin_amount);
+    ledger.jackpot -= reward_amount + admin_amount + reserve_amount;
+
+    // Large prizes are held in the dispute window instead of being paid right away
+    let dispute_config = DISPUTE_CONFIG.may_load(deps.storage)?;
+    let settlement = Settlement {
+        reward_amount,
+        admin_amount,
+        reserve_amount,
+        release_at: env
+            .block
+            .time
+            .seconds()
+            .saturating_add(dispute_config.as_ref().map_or(0, |config| config.period)),
+        disputed: false,
+    };
+    let held = dispute_config.is_some_and(|config| reward_amount > config.threshold);
+    if held {
+        ledger.settling += settlement.total();
+    }
+    LEDGER.save(deps.storage, &ledger)?;
+
+    let res = res
+        .add_attribute("shot_id", shot_id.to_string())
+        .add_attribute("pre_balance", pre_jackpot.to_string())
+        .add_attribute("jackpot_basis", session.jackpot_basis.to_string());
+    let res = if held {
+        finalize_shot(deps.storage, &mut session, ShotStatus::Settling)?;
+        record_resolution(deps.storage, env)?;
+        SETTLEMENTS.save(deps.storage, shot_id, &settlement)?;
+        res.add_event(shot_status_event(&session, env))
+            .add_attribute("release_at", settlement.release_at.to_string())
+    } else {
+        pay_settlement(deps.branch(), env, session, &settlement, res)?
+    };
+
+    // First resolved wins, every other pending shot is re-based on the remaining pool
+    let rebase_events = rebase_pending_shots(deps.storage, ledger.jackpot)?;
+    Ok(res.add_events(rebase_events))
+}
+
+// Credit the prize and distribute the fees of a goal
+fn pay_settlement(
+    deps: DepsMut,
+    env: &Env,
+    mut session: ShotSession,
+    settlement: &Settlement,
+    res: Response,
+) -> Result<Response, ContractError> {
+    let reward_amount = settlement.reward_amount;
+    let admin_amount = settlement.admin_amount;
+    let reserve_amount = settlement.reserve_amount;
+    LEDGER.update(deps.storage, |mut ledger| -> StdResult<_> {
+        ledger.reserve += reserve_amount;
+        ledger.claimable += reward_amount;
+        Ok(ledger)
+    })?;
+
+    // Consume the session before any payout is made
+    let was_pending = session.status == ShotStatus::Pending;
+    finalize_shot(deps.storage, &mut session, ShotStatus::Goal)?;
+    if was_pending {
+        record_resolution(deps.storage, env)?;
+    }
+
+    // The prize is credited to the player, who withdraws it with `Claim`
+    if reward_amount > 0 {
+        CLAIMABLE.update(deps.storage, &session.player, |claimable| -> StdResult<_> {
+            Ok(claimable.unwrap_or_default() + reward_amount)
+        })?;
+    }
+
+    let res = res.add_event(shot_status_event(&session, env));
 
     let mut attrs = vec![];
     let mut msgs = vec![];
-    // Transfer reward to the admin
+    // Split the admin fee across the fee recipients
     if admin_amount > 0 {
-        let admin = config.owner;
-        let asset = Asset::native(main_denom.clone(), admin_amount);
-        attrs.push(("reward_transfer_to_admin", admin_amount.to_string()));
-        msgs.push(asset.transfer_msg(admin)?);
+        let main_denom = MAIN_DENOM.load(deps.storage)?;
+        let fee_recipients = CONFIG.load(deps.storage)?.fee_recipients;
+        attrs.push(Attribute::new(
+            "reward_transfer_to_admin",
+            admin_amount.to_string(),
+        ));
+        let (fee_msgs, fee_attrs) =
+            fee_distribution_msgs(deps.storage, &fee_recipients, &main_denom, admin_amount)?;
+        msgs.extend(fee_msgs);
+        attrs.extend(fee_attrs);
     }
 
     if reward_amount > 0 {
-        let asset = Asset::native(main_denom, reward_amount);
-        attrs.push(("reward_transfer", reward_amount.to_string()));
-        msgs.push(asset.transfer_msg(player_address)?);
+        attrs.push(Attribute::new(
+            "reward_claimable",
+            reward_amount.to_string(),
+        ));
+    }
+
+    if reserve_amount > 0 {
+        attrs.push(Attribute::new("reserve_amount", reserve_amount.to_string()));
     }
 
-    Ok(res.add_attributes(attrs).add_messages(msgs))
+    Ok(res.add_attributes(attrs).add_submessages(msgs))
 }
 
-pub fn execute_set_admin_percentage(
-    deps: DepsMut,
-    info: MessageInfo,
-    new_admin_percentage: u8,
-) -> Result<Response, ContractError> {
-    let owner = CONFIG.load(deps.storage)?.owner;
-    if info.sender != owner {
-        return Err(ContractError::Unauthorized {});
-    }
-    CONFIG.update(deps.storage, |mut state| -> Result<_, ContractError> {
-        state.admin_percentage = new_admin_percentage;
-        Ok(state)
+// Take a held settlement out of the dispute window
+fn release_settlement(
+    storage: &mut dyn Storage,
+    shot_id: u64,
+) -> Result<(ShotSession, Settlement), ContractError> {
+    let settlement = SETTLEMENTS
+        .may_load(storage, shot_id)?
+        .ok_or(ContractError::SettlementNotFound { shot_id })?;
+    SETTLEMENTS.remove(storage, shot_id);
+    LEDGER.update(storage, |mut ledger| -> StdResult<_> {
+        ledger.settling -= settlement.total();
+        Ok(ledger)
     })?;
-    Ok(Response::new()
-        .add_attribute("method", "execute_set_admin_percentage")
-        .add_attribute("new_admin_percentage", new_admin_percentage.to_string()))
+    let session = SHOT_SESSIONS.load(storage, shot_id)?;
+    Ok((session, settlement))
 }
 
-pub fn execute_set_reward_percentage(
+// The guardian freezes a held goal until it resolves the dispute
+pub fn execute_dispute(
     deps: DepsMut,
     info: MessageInfo,
-    new_reward_percentage: u8,
+    env: Env,
+    shot_id: u64,
 ) -> Result<Response, ContractError> {
-    let owner = CONFIG.load(deps.storage)?.owner;
-    if info.sender != owner {
+    let guardian = DISPUTE_CONFIG
+        .may_load(deps.storage)?
+        .map(|config| config.guardian);
+    if guardian.as_ref() != Some(&info.sender) {
         return Err(ContractError::Unauthorized {});
     }
-    CONFIG.update(deps.storage, |mut state| -> Result<_, ContractError> {
-        state.reward_percentage = new_reward_percentage;
-        Ok(state)
-    })?;
+    let mut settlement = SETTLEMENTS
+        .may_load(deps.storage, shot_id)?
+        .ok_or(ContractError::SettlementNotFound { shot_id })?;
+    ensure!(
+        !settlement.disputed,
+        ContractError::ShotDisputed { shot_id }
+    );
+    ensure!(
+        env.block.time.seconds() < settlement.release_at,
+        ContractError::DisputeWindowClosed {
+            shot_id,
+            release_at: settlement.release_at
+        }
+    );
+    settlement.disputed = true;
+    SETTLEMENTS.save(deps.storage, shot_id, &settlement)?;
     Ok(Response::new()
-        .add_attribute("method", "execute_set_reward_percentage")
-        .add_attribute("new_reward_percentage", new_reward_percentage.to_string()))
+        .add_event(Event::new("shot_disputed").add_attribute("shot_id", shot_id.to_string()))
+        .add_attribute("method", "dispute")
+        .add_attribute("shot_id", shot_id.to_string()))
 }
 
-pub fn execute_set_ticket_price(
+pub fn execute_resolve_dispute(
     deps: DepsMut,
     info: MessageInfo,
-    new_ticket_price: u128,
+    env: Env,
+    shot_id: u64,
+    uphold: bool,
 ) -> Result<Response, ContractError> {
-    let owner = CONFIG.load(deps.storage)?.owner;
-    if info.sender != owner {
+    ensure_not_paused(deps.storage, &env, PauseTarget::Resolutions)?;
+    let guardian = DISPUTE_CONFIG
+        .may_load(deps.storage)?
+        .map(|config| config.guardian);
+    if guardian.as_ref() != Some(&info.sender) {
         return Err(ContractError::Unauthorized {});
     }
-    CONFIG.update(deps.storage, |mut state| -> Result<_, ContractError> {
-        state.ticket_price = new_ticket_price;
-        Ok(state)
+    let disputed = SETTLEMENTS
+        .may_load(deps.storage, shot_id)?
+        .is_some_and(|settlement| settlement.disputed);
+    ensure!(disputed, ContractError::ShotNotDisputed { shot_id });
+
+    let (mut session, settlement) = release_settlement(deps.storage, shot_id)?;
+    let res = Response::new()
+        .add_attribute("method", "resolve_dispute")
+        .add_attribute("uphold", uphold.to_string());
+    if uphold {
+        return pay_settlement(deps, &env, session, &settlement, res);
+    }
+
+    // The payout goes back to the jackpot and the operator who called the goal is slashed
+    LEDGER.update(deps.storage, |mut ledger| -> StdResult<_> {
+        ledger.jackpot += settlement.total();
+        Ok(ledger)
     })?;
-    Ok(Response::new()
-        .add_attribute("method", "execute_set_ticket_price")
-        .add_attribute("new_ticket_price", new_ticket_price.to_string()))
+    finalize_shot(deps.storage, &mut session, ShotStatus::Voided)?;
+    let slash_event = slash_operator(deps.storage, &env, shot_id, "voided_goal")?;
+    Ok(res
+        .add_event(shot_status_event(&session, &env))
+        .add_events(slash_event)
+        .add_attribute("shot_id", shot_id.to_string())
+        .add_attribute("returned_to_jackpot", settlement.total().to_string()))
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::QueryConfig {} => to_binary(&query_config(deps)?),
-        QueryMsg::QueryShootDeadline { address } => {
-            to_binary(&query_shoot_deadline(deps, address)?)
+// Anyone can release a held goal once its dispute window has passed
+pub fn execute_finalize(deps: DepsMut, env: Env, shot_id: u64) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.storage, &env, PauseTarget::Resolutions)?;
+    let settlement = SETTLEMENTS
+        .may_load(deps.storage, shot_id)?
+        .ok_or(ContractError::SettlementNotFound { shot_id })?;
+    ensure!(
+        !settlement.disputed,
+        ContractError::ShotDisputed { shot_id }
+    );
+    ensure!(
+        env.block.time.seconds() >= settlement.release_at,
+        ContractError::DisputeWindowOpen {
+            shot_id,
+            release_at: settlement.release_at
+        }
+    );
+
+    let (session, settlement) = release_settlement(deps.storage, shot_id)?;
+    let res = Response::new().add_attribute("method", "finalize");
+    pay_settlement(deps, &env, session, &settlement, res)
+}
+
+// Fan out `amount` to the weighted fee recipients, the last one takes the rounding remainder.
+// Transfers are sent with a reply, so a failing recipient is escrowed instead of reverting.
+fn fee_distribution_msgs(
+    storage: &mut dyn Storage,
+    fee_recipients: &[FeeRecipient],
+    denom: &str,
+    amount: u128,
+) -> StdResult<(Vec<SubMsg>, Vec<Attribute>)> {
+    let mut msgs = vec![];
+    let mut attrs = vec![];
+    let mut remaining = amount;
+    for (i, fee_recipient) in fee_recipients.iter().enumerate() {
+        let share = if i + 1 == fee_recipients.len() {
+            remaining
+        } else {
+            amount * fee_recipient.weight_bps as u128 / BPS_DENOMINATOR
+        };
+        remaining -= share;
+        if share == 0 {
+            continue;
+        }
+        match &fee_recipient.destination {
+            FeeDestination::Address(address) => {
+                let asset = Asset::native(denom, share);
+                attrs.push(Attribute::new(
+                    "fee_transfer",
+                    format!("{}:{}", address, share),
+                ));
+                let payout_id = PAYOUT_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+                PAYOUT_COUNT.save(storage, &payout_id)?;
+                PENDING_PAYOUTS.save(storage, payout_id, &(address.clone(), share))?;
+                msgs.push(SubMsg::reply_always(
+                    asset.transfer_msg(address)?,
+                    payout_id,
+                ));
+            }
+            FeeDestination::Burn => {
+                attrs.push(Attribute::new("fee_burn", share.to_string()));
+                let payout_id = PAYOUT_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+                PAYOUT_COUNT.save(storage, &payout_id)?;
+                PENDING_BURNS.save(storage, payout_id, &share)?;
+                msgs.push(SubMsg::reply_always(
+                    BankMsg::Burn {
+                        amount: vec![Coin::new(share, denom)],
+                    },
+                    payout_id,
+                ));
+            }
         }
-        QueryMsg::QueryBalance {} => to_binary(&query_balance(deps, env)?),
     }
+    Ok((msgs, attrs))
 }
 
-fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
-    let config = CONFIG.load(deps.storage)?;
-    Ok(ConfigResponse {
-        owner: config.owner.to_string(),
-        ticket_price: config.ticket_price,
-        reward_percentage: config.reward_percentage,
-        admin_percentage: config.admin_percentage,
-        shoot_duration: config.shoot_duration,
-    })
+// Take `bps` of `amount`, carrying the remainder of the integer division into the next split
+fn split_with_dust(amount: u128, bps: u16, dust: &mut u128) -> u128 {
+    let scaled = amount * bps as u128 + *dust;
+    *dust = scaled % BPS_DENOMINATOR;
+    scaled / BPS_DENOMINATOR
 }
 
-fn query_shoot_deadline(deps: Deps, address: Addr) -> StdResult<ShootDeadlineResponse> {
-    let shoot_deadline = SHOOT_DEADLINE_MAPPER.load(deps.storage, address)?;
-    Ok(ShootDeadlineResponse {
-        shoot_deadline: shoot_deadline,
-    })
+// Lower the locked jackpot of pending shots to what the pool can still pay
+fn rebase_pending_shots(storage: &mut dyn Storage, remaining_pool: u128) -> StdResult<Vec<Event>> {
+    let pending_ids = PENDING_SHOTS
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<u64>>>()?;
+
+    let mut events = vec![];
+    for shot_id in pending_ids {
+        let mut session = SHOT_SESSIONS.load(storage, shot_id)?;
+        if session.jackpot_basis <= remaining_pool {
+            continue;
+        }
+        session.jackpot_basis = remaining_pool;
+        SHOT_SESSIONS.save(storage, shot_id, &session)?;
+        events.push(
+            Event::new("shot_rebased")
+                .add_attribute("shot_id", shot_id.to_string())
+                .add_attribute("jackpot_basis", remaining_pool.to_string())
+                .add_attribute("reward_amount", session.reward_amount().to_string()),
+        );
+    }
+    Ok(events)
 }
 
-fn query_balance(deps: Deps, env: Env) -> StdResult<ContractBalanceResponse> {
+pub fn execute_claim(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let amount = CLAIMABLE
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    ensure!(amount > 0, ContractError::NothingToClaim {});
+    CLAIMABLE.remove(deps.storage, &info.sender);
+    LEDGER.update(deps.storage, |mut ledger| -> StdResult<_> {
+        ledger.claimable -= amount;
+        Ok(ledger)
+    })?;
+
     let main_denom = MAIN_DENOM.load(deps.storage)?;
-    let balance = deps
-        .querier
-        .query_balance(&env.contract.address, &main_denom.to_string())?;
-    Ok(ContractBalanceResponse {
-        amount: balance.amount.u128(),
-    })
+    let asset = Asset::native(main_denom, amount);
+    Ok(Response::new()
+        .add_attribute("method", "claim")
+        .add_attribute("recipient", info.sender.to_string())
+        .add_attribute("amount", amount.to_string())
+        .add_message(asset.transfer_msg(info.sender)?))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use cosmwasm_std::testing::{
-        mock_dependencies, mock_dependencies_with_balances, mock_env, mock_info,
+pub fn execute_withdraw_fees(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    amount: u128,
+    recipient: Option<String>,
+) -> Result<Response, ContractError> {
+    ensure_role(deps.storage, &info.sender, Role::Treasury)?;
+    ensure_not_paused(deps.storage, &env, PauseTarget::FeeWithdrawals)?;
+    let recipient = match recipient {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => info.sender,
     };
-    use cosmwasm_std::{attr, from_binary, Addr, Coin, Timestamp, Uint128};
-
-    #[test]
-    fn test_proper_initialization() {
-        let mut deps = mock_dependencies();
-        //no owner specified in the instantiation message
-        let msg = InstantiateMsg {
-            owner: None,
-            main_denom: "usei".to_string(),
-        };
-        let env = mock_env();
-        let info = mock_info("creator", &[]);
 
-        let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
-        assert_eq!(0, res.messages.len());
+    let mut ledger = LEDGER.load(deps.storage)?;
+    ensure!(
+        amount > 0 && amount <= ledger.admin_fees,
+        ContractError::InsufficientBalance {}
+    );
+    ledger.admin_fees -= amount;
+    ledger.fees_withdrawn += amount;
+    LEDGER.save(deps.storage, &ledger)?;
 
-        // it worked, let's query the state
-        let state = CONFIG.load(&deps.storage).unwrap();
-        assert_eq!(
-            state,
-            Config {
-                owner: Addr::unchecked("creator".to_string()),
-                ticket_price: 0,
-                reward_percentage: 80,
-                admin_percentage: 4,
-                shoot_duration: 90
-            }
-        );
-        //specifying an owner address in the instantiation message
-        let msg = InstantiateMsg {
-            owner: Some("specified_owner".to_string()),
-            main_denom: "usei".to_string(),
-        };
+    let main_denom = MAIN_DENOM.load(deps.storage)?;
+    let asset = Asset::native(main_denom, amount);
+    Ok(Response::new()
+        .add_attribute("method", "withdraw_fees")
+        .add_attribute("recipient", recipient.to_string())
+        .add_attribute("amount", amount.to_string())
+        .add_message(asset.transfer_msg(recipient)?))
+}
 
-        let res = instantiate(deps.as_mut(), env, info, msg).unwrap();
-        assert_eq!(0, res.messages.len());
+pub fn execute_release_reserve(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    amount: u128,
+    recipient: Option<String>,
+) -> Result<Response, ContractError> {
+    ensure_role(deps.storage, &info.sender, Role::Treasury)?;
+    let mut ledger = LEDGER.load(deps.storage)?;
+    ensure!(
+        amount > 0 && amount <= ledger.reserve,
+        ContractError::InsufficientBalance {}
+    );
+    ledger.reserve -= amount;
 
-        // it worked, let's query the state
+    let res = Response::new()
+        .add_attribute("method", "release_reserve")
+        .add_attribute("amount", amount.to_string());
+    let recipient = match recipient {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => {
+            ledger.jackpot += amount;
+            LEDGER.save(deps.storage, &ledger)?;
+            return Ok(res.add_attribute("recipient", "jackpot"));
+        }
+    };
+    // Paying the reserve out is a withdrawal like the admin fees
+    ensure_not_paused(deps.storage, &env, PauseTarget::FeeWithdrawals)?;
+    LEDGER.save(deps.storage, &ledger)?;
+
+    let main_denom = MAIN_DENOM.load(deps.storage)?;
+    let asset = Asset::native(main_denom, amount);
+    Ok(res
+        .add_attribute("recipient", recipient.to_string())
+        .add_message(asset.transfer_msg(recipient)?))
+}
+
+pub fn execute_miss_shot(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    shot_id: u64,
+    attestation: Option<ResultAttestation>,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.storage, &env, PauseTarget::Resolutions)?;
+    let session = load_pending_shot(deps.storage, shot_id)?;
+    assert_referee_result(
+        deps.as_ref(),
+        &info,
+        &env,
+        &session,
+        ShotStatus::Miss,
+        attestation,
+    )?;
+
+    let res = Response::new().add_attribute("method", "miss_shot");
+    settle_miss(deps.storage, &env, session, res)
+}
+
+fn settle_miss(
+    storage: &mut dyn Storage,
+    env: &Env,
+    mut session: ShotSession,
+    res: Response,
+) -> Result<Response, ContractError> {
+    finalize_shot(storage, &mut session, ShotStatus::Miss)?;
+    record_resolution(storage, env)?;
+    Ok(res
+        .add_event(shot_status_event(&session, env))
+        .add_attribute("shot_id", session.id.to_string()))
+}
+
+// The operator commits sha256 hashes of seeds that are revealed once bound to a shot
+pub fn execute_commit_seeds(
+    deps: DepsMut,
+    info: MessageInfo,
+    commitments: Vec<HexBinary>,
+) -> Result<Response, ContractError> {
+    ensure_role(deps.storage, &info.sender, Role::Referee)?;
+
+    let first_id = COMMITMENT_COUNT.load(deps.storage)? + 1;
+    let mut commitment_id = first_id;
+    for commitment in commitments {
+        ensure!(
+            commitment.len() == 32,
+            ContractError::InvalidSeedCommitment {}
+        );
+        let seed_commitment = SeedCommitment {
+            commitment,
+            shot_id: None,
+            server_seed: None,
+        };
+        SEED_COMMITMENTS.save(deps.storage, commitment_id, &seed_commitment)?;
+        commitment_id += 1;
+    }
+    COMMITMENT_COUNT.save(deps.storage, &(commitment_id - 1))?;
+
+    Ok(Response::new()
+        .add_attribute("method", "commit_seeds")
+        .add_attribute("first_commitment_id", first_id.to_string())
+        .add_attribute("count", (commitment_id - first_id).to_string()))
+}
+
+// Anyone holding the operator seed can resolve a commit-reveal or physics shot,
+// once the beacon of its round is out
+pub fn execute_reveal_shot(
+    deps: DepsMut,
+    env: Env,
+    shot_id: u64,
+    server_seed: HexBinary,
+    signature: HexBinary,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.storage, &env, PauseTarget::Resolutions)?;
+    let session = load_pending_shot(deps.storage, shot_id)?;
+    let (commitment_id, round) = match &session.resolution {
+        ShotResolution::CommitReveal {
+            commitment_id,
+            round,
+            ..
+        }
+        | ShotResolution::Physics {
+            commitment_id,
+            round,
+            ..
+        } => (*commitment_id, *round),
+        resolution => {
+            return Err(ContractError::WrongResolutionMode {
+                shot_id,
+                mode: resolution.mode().as_str().to_string(),
+            })
+        }
+    };
+    if env.block.time.seconds() >= session.deadline {
+        return Err(ContractError::ShootDeadlinePassed {});
+    }
+
+    // The seed must be the one committed before the player shot
+    let mut commitment = SEED_COMMITMENTS.load(deps.storage, commitment_id)?;
+    ensure!(
+        sha256(server_seed.as_slice()) == commitment.commitment.as_slice(),
+        ContractError::InvalidReveal { shot_id }
+    );
+    verify_drand_beacon(deps.storage, round, &signature)?;
+    commitment.server_seed = Some(server_seed.clone());
+    SEED_COMMITMENTS.save(deps.storage, commitment_id, &commitment)?;
+    unlock_prize_bond(
+        deps.storage,
+        session.resolution.prize_bond().unwrap_or_default(),
+    )?;
+
+    let res = Response::new()
+        .add_attribute("method", "reveal_shot")
+        .add_attribute("round", round.to_string());
+    let (goal, res) = match &session.resolution {
+        ShotResolution::Physics {
+            kick,
+            goal_probability_bps,
+            ..
+        } => {
+            // The seed carries the keeper position committed before the kick
+            let keeper = keeper_position(server_seed.as_slice())
+                .ok_or(ContractError::InvalidReveal { shot_id })?;
+            let result = physics::simulate(kick, keeper);
+            let randomness =
+                physics_shot_randomness(server_seed.as_slice(), shot_id, signature.as_slice());
+            let roll = roll_bps(&randomness);
+            let res = res
+                .add_attribute("keeper_position", keeper.to_string())
+                .add_attribute("ball_position", physics::ball_position(kick).to_string())
+                .add_attribute("kick_result", result.as_str())
+                .add_attribute("roll", roll.to_string());
+            (
+                result == KickResult::Goal && roll < *goal_probability_bps,
+                res,
+            )
+        }
+        ShotResolution::CommitReveal {
+            client_seed,
+            goal_probability_bps,
+            ..
+        } => {
+            let randomness = commit_reveal_randomness(
+                server_seed.as_slice(),
+                client_seed,
+                shot_id,
+                signature.as_slice(),
+            );
+            let roll = roll_bps(&randomness);
+            (
+                roll < *goal_probability_bps,
+                res.add_attribute("roll", roll.to_string()),
+            )
+        }
+        _ => unreachable!(),
+    };
+    if goal {
+        settle_goal(deps, &env, session, res)
+    } else {
+        settle_miss(deps.storage, &env, session, res)
+    }
+}
+
+// Anyone can resolve a drand shot with the signature of the round it is bound to, even
+// after the deadline since the outcome no longer depends on anyone's goodwill
+pub fn execute_resolve_drand_shot(
+    deps: DepsMut,
+    env: Env,
+    shot_id: u64,
+    signature: HexBinary,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.storage, &env, PauseTarget::Resolutions)?;
+    let session = load_pending_shot(deps.storage, shot_id)?;
+    let (round, goal_probability_bps) = match &session.resolution {
+        ShotResolution::Drand {
+            round,
+            goal_probability_bps,
+        } => (*round, *goal_probability_bps),
+        resolution => {
+            return Err(ContractError::WrongResolutionMode {
+                shot_id,
+                mode: resolution.mode().as_str().to_string(),
+            })
+        }
+    };
+    verify_drand_beacon(deps.storage, round, &signature)?;
+
+    let randomness = drand_shot_randomness(signature.as_slice(), shot_id);
+    let roll = roll_bps(&randomness);
+    let res = Response::new()
+        .add_attribute("method", "resolve_drand_shot")
+        .add_attribute("round", round.to_string())
+        .add_attribute("roll", roll.to_string());
+    if roll < goal_probability_bps {
+        settle_goal(deps, &env, session, res)
+    } else {
+        settle_miss(deps.storage, &env, session, res)
+    }
+}
+
+fn randomness_job_id(shot_id: u64) -> String {
+    format!("shot-{}", shot_id)
+}
+
+// Callback of the randomness proxy, only the configured proxy may deliver randomness
+pub fn execute_nois_receive(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    callback: NoisCallback,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.storage, &env, PauseTarget::Resolutions)?;
+    let proxy = RANDOMNESS_PROXY
+        .may_load(deps.storage)?
+        .ok_or(ContractError::RandomnessProxyNotConfigured {})?;
+    if info.sender != proxy {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let unknown_job = || ContractError::UnknownRandomnessJob {
+        job_id: callback.job_id.clone(),
+    };
+    let shot_id = callback
+        .job_id
+        .strip_prefix("shot-")
+        .and_then(|shot_id| shot_id.parse::<u64>().ok())
+        .ok_or_else(unknown_job)?;
+    let session = load_pending_shot(deps.storage, shot_id)?;
+    let goal_probability_bps = match &session.resolution {
+        ShotResolution::RandomnessProxy {
+            job_id,
+            goal_probability_bps,
+        } if *job_id == callback.job_id => *goal_probability_bps,
+        _ => return Err(unknown_job()),
+    };
+
+    let randomness: [u8; 32] = callback
+        .randomness
+        .to_array()
+        .map_err(|_| ContractError::InvalidRandomness {})?;
+    let roll = roll_bps(&randomness);
+    let res = Response::new()
+        .add_attribute("method", "nois_receive")
+        .add_attribute("job_id", callback.job_id)
+        .add_attribute("roll", roll.to_string());
+    if roll < goal_probability_bps {
+        settle_goal(deps, &env, session, res)
+    } else {
+        settle_miss(deps.storage, &env, session, res)
+    }
+}
+
+// The owner posts a bond as operator of the game
+pub fn execute_bond(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    ensure_owner(deps.storage, &info.sender)?;
+    let main_denom = MAIN_DENOM.load(deps.storage)?;
+    ensure!(
+        info.funds.len() == 1
+            && info.funds[0].denom == main_denom
+            && !info.funds[0].amount.is_zero(),
+        ContractError::InvalidFund {}
+    );
+    let amount = info.funds[0].amount.u128();
+
+    let mut bond = OPERATOR_BOND.load(deps.storage)?;
+    // A previous owner's bond stays theirs until they withdraw it
+    if let Some(operator) = &bond.operator {
+        ensure!(
+            *operator == info.sender || bond.bonded + bond.unbonding == 0,
+            ContractError::BondHeldByOperator {
+                operator: operator.to_string()
+            }
+        );
+    }
+    bond.operator = Some(info.sender);
+    bond.bonded += amount;
+    OPERATOR_BOND.save(deps.storage, &bond)?;
+    LEDGER.update(deps.storage, |mut ledger| -> StdResult<_> {
+        ledger.bond += amount;
+        Ok(ledger)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("method", "bond")
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("bonded", bond.bonded.to_string()))
+}
+
+// Start unbonding part of the bond, an earlier unbonding is merged and its period restarted
+pub fn execute_unbond(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    amount: u128,
+) -> Result<Response, ContractError> {
+    let mut bond = OPERATOR_BOND.load(deps.storage)?;
+    ensure_bond_operator(&bond, &info.sender)?;
+    // The part backing pending prizes stays bonded until their seeds are revealed
+    ensure!(
+        amount > 0 && amount <= bond.unlocked(),
+        ContractError::InsufficientBalance {}
+    );
+    let unbonding_period = BOND_CONFIG.load(deps.storage)?.unbonding_period;
+    bond.bonded -= amount;
+    bond.unbonding += amount;
+    bond.unbonding_release_at = env.block.time.seconds().saturating_add(unbonding_period);
+    OPERATOR_BOND.save(deps.storage, &bond)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "unbond")
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("release_at", bond.unbonding_release_at.to_string()))
+}
+
+pub fn execute_withdraw_bond(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+) -> Result<Response, ContractError> {
+    let mut bond = OPERATOR_BOND.load(deps.storage)?;
+    ensure_bond_operator(&bond, &info.sender)?;
+    ensure!(bond.unbonding > 0, ContractError::InsufficientBalance {});
+    ensure!(
+        env.block.time.seconds() >= bond.unbonding_release_at,
+        ContractError::BondUnbonding {
+            release_at: bond.unbonding_release_at
+        }
+    );
+    // A goal that can still be disputed may still slash the bond
+    ensure!(
+        !settlement_open(deps.storage, env.block.time.seconds()),
+        ContractError::DisputePending {}
+    );
+
+    let amount = bond.unbonding;
+    bond.unbonding = 0;
+    if bond.bonded == 0 {
+        bond.operator = None;
+    }
+    OPERATOR_BOND.save(deps.storage, &bond)?;
+    LEDGER.update(deps.storage, |mut ledger| -> StdResult<_> {
+        ledger.bond -= amount;
+        Ok(ledger)
+    })?;
+
+    let main_denom = MAIN_DENOM.load(deps.storage)?;
+    let asset = Asset::native(main_denom, amount);
+    Ok(Response::new()
+        .add_attribute("method", "withdraw_bond")
+        .add_attribute("amount", amount.to_string())
+        .add_message(asset.transfer_msg(info.sender)?))
+}
+
+fn ensure_bond_operator(bond: &OperatorBond, sender: &Addr) -> Result<(), ContractError> {
+    match &bond.operator {
+        Some(operator) if operator == sender => Ok(()),
+        _ => Err(ContractError::Unauthorized {}),
+    }
+}
+
+// The operator leaves with the ownership, their bond stays slashable until it is withdrawn
+// and the part backing pending prizes stays bonded until their seeds are revealed
+fn unbond_previous_operator(storage: &mut dyn Storage, env: &Env) -> StdResult<()> {
+    let mut bond = OPERATOR_BOND.load(storage)?;
+    let amount = bond.unlocked();
+    if amount == 0 {
+        return Ok(());
+    }
+    let unbonding_period = BOND_CONFIG.load(storage)?.unbonding_period;
+    bond.unbonding += amount;
+    bond.bonded -= amount;
+    bond.unbonding_release_at = env.block.time.seconds().saturating_add(unbonding_period);
+    OPERATOR_BOND.save(storage, &bond)
+}
+
+// Move `slash_bps` of the operator bond into the jackpot, bonded funds go first
+fn slash_operator(
+    storage: &mut dyn Storage,
+    env: &Env,
+    shot_id: u64,
+    reason: &str,
+) -> StdResult<Option<Event>> {
+    let bond = OPERATOR_BOND.load(storage)?;
+    let slash_bps = BOND_CONFIG.load(storage)?.slash_bps;
+    let amount = (bond.bonded + bond.unbonding) * slash_bps as u128 / BPS_DENOMINATOR;
+    if amount == 0 {
+        return Ok(None);
+    }
+    LEDGER.update(storage, |mut ledger| -> StdResult<_> {
+        ledger.jackpot += amount;
+        Ok(ledger)
+    })?;
+    debit_bond(storage, env, shot_id, amount, reason).map(Some)
+}
+
+// The player of a shot whose seed was withheld gets the prize it could still win
+// out of the operator bond
+fn pay_prize_from_bond(
+    storage: &mut dyn Storage,
+    env: &Env,
+    session: &ShotSession,
+    prize_bond: u128,
+) -> StdResult<Option<Event>> {
+    unlock_prize_bond(storage, prize_bond)?;
+    let bond = OPERATOR_BOND.load(storage)?;
+    let amount = session
+        .reward_amount()
+        .min(prize_bond)
+        .min(bond.bonded + bond.unbonding);
+    if amount == 0 {
+        return Ok(None);
+    }
+    LEDGER.update(storage, |mut ledger| -> StdResult<_> {
+        ledger.claimable += amount;
+        Ok(ledger)
+    })?;
+    CLAIMABLE.update(storage, &session.player, |claimable| -> StdResult<_> {
+        Ok(claimable.unwrap_or_default() + amount)
+    })?;
+    let event = debit_bond(storage, env, session.id, amount, "unrevealed_seed")?;
+    Ok(Some(event.add_attribute("player", session.player.as_str())))
+}
+
+// Take `amount` out of the operator bond and record it, bonded funds go first
+fn debit_bond(
+    storage: &mut dyn Storage,
+    env: &Env,
+    shot_id: u64,
+    amount: u128,
+    reason: &str,
+) -> StdResult<Event> {
+    let mut bond = OPERATOR_BOND.load(storage)?;
+    let from_bonded = amount.min(bond.bonded);
+    bond.bonded -= from_bonded;
+    bond.unbonding -= amount - from_bonded;
+    bond.total_slashed += amount;
+    OPERATOR_BOND.save(storage, &bond)?;
+    LEDGER.update(storage, |mut ledger| -> StdResult<_> {
+        ledger.bond -= amount;
+        Ok(ledger)
+    })?;
+
+    let slash_id = SLASH_COUNT.load(storage)? + 1;
+    SLASH_COUNT.save(storage, &slash_id)?;
+    let record = SlashRecord {
+        shot_id,
+        amount,
+        reason: reason.to_string(),
+        timestamp: env.block.time.seconds(),
+    };
+    SLASHES.save(storage, slash_id, &record)?;
+
+    Ok(Event::new("operator_slashed")
+        .add_attribute("slash_id", slash_id.to_string())
+        .add_attribute("shot_id", shot_id.to_string())
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("reason", reason))
+}
+
+// Back the prize of a commit-reveal or physics shot with the free part of the bond
+fn lock_prize_bond(storage: &mut dyn Storage, prize: u128) -> Result<(), ContractError> {
+    let mut bond = OPERATOR_BOND.load(storage)?;
+    ensure!(
+        prize <= bond.unlocked(),
+        ContractError::InsufficientBond {
+            required: prize,
+            available: bond.unlocked(),
+        }
+    );
+    bond.locked += prize;
+    OPERATOR_BOND.save(storage, &bond)?;
+    Ok(())
+}
+
+fn unlock_prize_bond(storage: &mut dyn Storage, prize: u128) -> StdResult<()> {
+    OPERATOR_BOND.update(storage, |mut bond| -> StdResult<_> {
+        bond.locked = bond.locked.saturating_sub(prize);
+        Ok(bond)
+    })?;
+    Ok(())
+}
+
+// Anyone can sweep pending shots whose deadline has passed into `Expired`
+pub fn execute_expire_shots(
+    deps: DepsMut,
+    env: Env,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    // Expiring charges the operator bond for seeds they could not reveal while paused
+    ensure_not_paused(deps.storage, &env, PauseTarget::Resolutions)?;
+    let limit = limit.unwrap_or(DEFAULT_EXPIRE_LIMIT).min(MAX_EXPIRE_LIMIT) as usize;
+    let now = env.block.time.seconds();
+
+    let expired_ids = PENDING_SHOTS
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|item| item.as_ref().map_or(true, |(_, deadline)| *deadline <= now))
+        .map(|item| item.map(|(shot_id, _)| shot_id))
+        // Shots anyone can resolve stay pending until they are
+        .filter(|item| {
+            item.as_ref().map_or(true, |shot_id| {
+                SHOT_SESSIONS
+                    .load(deps.storage, *shot_id)
+                    .map_or(true, |session| !session.resolution.is_permissionless())
+            })
+        })
+        .take(limit)
+        .collect::<StdResult<Vec<u64>>>()?;
+
+    let mut events = vec![];
+    for shot_id in expired_ids.iter() {
+        let mut session = SHOT_SESSIONS.load(deps.storage, *shot_id)?;
+        finalize_shot(deps.storage, &mut session, ShotStatus::Expired)?;
+        events.push(shot_status_event(&session, &env));
+        // The operator withheld the seed committed for this shot
+        if let Some(prize_bond) = session.resolution.prize_bond() {
+            events.extend(pay_prize_from_bond(
+                deps.storage,
+                &env,
+                &session,
+                prize_bond,
+            )?);
+        }
+    }
+    release_liveness(deps.storage)?;
+
+    Ok(Response::new()
+        .add_events(events)
+        .add_attribute("method", "expire_shots")
+        .add_attribute("expired_count", expired_ids.len().to_string()))
+}
+
+// The player takes back the ticket of a shot nobody resolved in time
+pub fn execute_refund_shot(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    shot_id: u64,
+) -> Result<Response, ContractError> {
+    let mut session = SHOT_SESSIONS
+        .may_load(deps.storage, shot_id)?
+        .ok_or(ContractError::ShotNotFound { shot_id })?;
+    if info.sender != session.player {
+        return Err(ContractError::Unauthorized {});
+    }
+    if session.status != ShotStatus::Pending && session.status != ShotStatus::Expired {
+        return Err(ContractError::ShotAlreadyResolved {
+            shot_id,
+            status: session.status.as_str().to_string(),
+        });
+    }
+    // The player could check the public outcome first and only refund a miss,
+    // and a withheld seed is paid out of the operator bond instead
+    ensure!(
+        !session.resolution.is_permissionless() && session.resolution.prize_bond().is_none(),
+        ContractError::RefundNotAllowed {
+            shot_id,
+            mode: session.resolution.mode().as_str().to_string(),
+        }
+    );
+    // Every unresolved ticket is refundable right away in degraded mode
+    let config = CONFIG.load(deps.storage)?;
+    let now = env.block.time.seconds();
+    let degraded = LIVENESS
+        .load(deps.storage)?
+        .is_degraded(now, config.liveness_period);
+    let available_at = session.deadline.saturating_add(config.refund_grace_period);
+    ensure!(
+        degraded || now >= available_at,
+        ContractError::RefundNotAvailable {
+            shot_id,
+            available_at
+        }
+    );
+
+    // Take the ticket back out of the buckets it was credited to, as far as they still hold it
+    let mut ledger = LEDGER.load(deps.storage)?;
+    let fee_part = session.ticket_fee.min(ledger.admin_fees);
+    let jackpot_part = (session.paid_amount - fee_part).min(ledger.jackpot);
+    ledger.admin_fees -= fee_part;
+    ledger.fees_accrued = ledger.fees_accrued.saturating_sub(fee_part);
+    ledger.jackpot -= jackpot_part;
+    LEDGER.save(deps.storage, &ledger)?;
+    let refund_amount = fee_part + jackpot_part;
+
+    finalize_shot(deps.storage, &mut session, ShotStatus::Refunded)?;
+    release_liveness(deps.storage)?;
+    let rebase_events = rebase_pending_shots(deps.storage, ledger.jackpot)?;
+
+    let mut res = Response::new()
+        .add_event(shot_status_event(&session, &env))
+        .add_events(rebase_events)
+        .add_attribute("method", "refund_shot")
+        .add_attribute("shot_id", shot_id.to_string())
+        .add_attribute("amount", refund_amount.to_string());
+    if refund_amount > 0 {
+        let main_denom = MAIN_DENOM.load(deps.storage)?;
+        let asset = Asset::native(main_denom, refund_amount);
+        res = res.add_message(asset.transfer_msg(session.player)?);
+    }
+    Ok(res)
+}
+
+pub fn execute_check_liveness(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let liveness_period = CONFIG.load(deps.storage)?.liveness_period;
+    let mut liveness = LIVENESS.load(deps.storage)?;
+    let mut res = Response::new().add_attribute("method", "check_liveness");
+    if !liveness.degraded && liveness.is_degraded(env.block.time.seconds(), liveness_period) {
+        liveness.degraded = true;
+        LIVENESS.save(deps.storage, &liveness)?;
+        res = res.add_event(Event::new("degraded_mode").add_attribute(
+            "awaiting_since",
+            liveness.awaiting_since.unwrap_or_default().to_string(),
+        ));
+    }
+    Ok(res.add_attribute("degraded", liveness.degraded.to_string()))
+}
+
+// The owner leaves degraded mode once no shot is left waiting for a resolution
+pub fn execute_resume_normal_mode(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    ensure_role(deps.storage, &info.sender, Role::Admin)?;
+    let pending = PENDING_SHOTS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .count() as u32;
+    ensure!(pending == 0, ContractError::BacklogNotResolved { pending });
+    LIVENESS.save(deps.storage, &Liveness::default())?;
+    Ok(Response::new()
+        .add_event(Event::new("normal_mode"))
+        .add_attribute("method", "resume_normal_mode"))
+}
+
+pub fn execute_pause(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    what: PauseTarget,
+) -> Result<Response, ContractError> {
+    ensure_role(deps.storage, &info.sender, Role::Pauser)?;
+    let now = env.block.time.seconds();
+    // Pausing again keeps the original pause time and cancels a scheduled release
+    let paused_at = match PAUSES.may_load(deps.storage, what.as_str())? {
+        Some(pause) if pause.is_active(now) => pause.paused_at,
+        _ => now,
+    };
+    PAUSES.save(
+        deps.storage,
+        what.as_str(),
+        &Pause {
+            paused_at,
+            paused_by: info.sender.clone(),
+            unpause_at: None,
+        },
+    )?;
+    Ok(Response::new()
+        .add_event(
+            Event::new("paused")
+                .add_attribute("what", what.as_str())
+                .add_attribute("paused_by", info.sender.to_string()),
+        )
+        .add_attribute("method", "pause")
+        .add_attribute("what", what.as_str()))
+}
+
+pub fn execute_unpause(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    what: PauseTarget,
+    unpause_at: Option<u64>,
+) -> Result<Response, ContractError> {
+    ensure_role(deps.storage, &info.sender, Role::Admin)?;
+    let now = env.block.time.seconds();
+    let mut pause = PAUSES
+        .may_load(deps.storage, what.as_str())?
+        .filter(|pause| pause.is_active(now))
+        .ok_or(ContractError::NotPaused {
+            what: what.as_str().to_string(),
+        })?;
+
+    let res = Response::new()
+        .add_attribute("method", "unpause")
+        .add_attribute("what", what.as_str());
+    match unpause_at {
+        Some(unpause_at) if unpause_at > now => {
+            pause.unpause_at = Some(unpause_at);
+            PAUSES.save(deps.storage, what.as_str(), &pause)?;
+            Ok(res.add_event(
+                Event::new("unpause_scheduled")
+                    .add_attribute("what", what.as_str())
+                    .add_attribute("unpause_at", unpause_at.to_string()),
+            ))
+        }
+        _ => {
+            PAUSES.remove(deps.storage, what.as_str());
+            Ok(res.add_event(Event::new("unpaused").add_attribute("what", what.as_str())))
+        }
+    }
+}
+
+// Sweep funds the contract holds outside of the ledger (donations, accidental transfers)
+pub fn execute_sync_balance(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    bucket: PoolBucket,
+) -> Result<Response, ContractError> {
+    ensure_role(deps.storage, &info.sender, Role::Treasury)?;
+
+    let mut ledger = LEDGER.load(deps.storage)?;
+    let unaccounted = query_unaccounted(deps.as_ref(), &env, &ledger)?;
+    match bucket {
+        PoolBucket::Jackpot => ledger.jackpot += unaccounted,
+        PoolBucket::AdminFees => ledger.admin_fees += unaccounted,
+        PoolBucket::Reserve => ledger.reserve += unaccounted,
+    }
+    LEDGER.save(deps.storage, &ledger)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "execute_sync_balance")
+        .add_attribute("bucket", format!("{:?}", bucket))
+        .add_attribute("amount", unaccounted.to_string()))
+}
+
+// The proposed owner has to accept, so a mistyped address can never take control
+pub fn execute_propose_new_owner(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    address: String,
+    expiry: Option<u64>,
+) -> Result<Response, ContractError> {
+    ensure_owner(deps.storage, &info.sender)?;
+    let address = deps.api.addr_validate(&address)?;
+    if let Some(expires_at) = expiry {
+        ensure!(
+            expires_at > env.block.time.seconds(),
+            ContractError::InvalidExpiry {}
+        );
+    }
+    PENDING_OWNER.save(
+        deps.storage,
+        &PendingOwner {
+            address: address.clone(),
+            expires_at: expiry,
+        },
+    )?;
+    Ok(Response::new()
+        .add_attribute("method", "execute_propose_new_owner")
+        .add_attribute("pending_owner", address)
+        .add_attribute(
+            "expires_at",
+            expiry.map_or("never".to_string(), |expires_at| expires_at.to_string()),
+        ))
+}
+
+// Fee recipients are left as they are, the new owner updates them if they
+// still point at the previous owner
+pub fn execute_accept_ownership(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+) -> Result<Response, ContractError> {
+    let pending_owner = PENDING_OWNER
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingOwner {})?;
+    if info.sender != pending_owner.address {
+        return Err(ContractError::Unauthorized {});
+    }
+    if let Some(expires_at) = pending_owner.expires_at {
+        ensure!(
+            env.block.time.seconds() < expires_at,
+            ContractError::OwnershipProposalExpired { expires_at }
+        );
+    }
+    let mut config = CONFIG.load(deps.storage)?;
+    let previous_owner = config.owner.replace(info.sender.clone());
+    CONFIG.save(deps.storage, &config)?;
+    PENDING_OWNER.remove(deps.storage);
+    unbond_previous_operator(deps.storage, &env)?;
+    Ok(Response::new()
+        .add_event(
+            Event::new("ownership_transferred")
+                .add_attribute(
+                    "previous_owner",
+                    previous_owner.map_or("none".to_string(), |owner| owner.to_string()),
+                )
+                .add_attribute("new_owner", info.sender.to_string()),
+        )
+        .add_attribute("method", "execute_accept_ownership"))
+}
+
+pub fn execute_cancel_ownership_proposal(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    ensure_owner(deps.storage, &info.sender)?;
+    ensure!(
+        PENDING_OWNER.may_load(deps.storage)?.is_some(),
+        ContractError::NoPendingOwner {}
+    );
+    PENDING_OWNER.remove(deps.storage);
+    Ok(Response::new().add_attribute("method", "execute_cancel_ownership_proposal"))
+}
+
+// Give up the owner role for good, every owner-only message is disabled afterwards
+pub fn execute_renounce_ownership(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+) -> Result<Response, ContractError> {
+    ensure_owner(deps.storage, &info.sender)?;
+    CONFIG.update(deps.storage, |mut state| -> Result<_, ContractError> {
+        state.owner = None;
+        Ok(state)
+    })?;
+    PENDING_OWNER.remove(deps.storage);
+    unbond_previous_operator(deps.storage, &env)?;
+    Ok(Response::new()
+        .add_event(
+            Event::new("ownership_transferred")
+                .add_attribute("previous_owner", info.sender.to_string())
+                .add_attribute("new_owner", "none"),
+        )
+        .add_attribute("method", "execute_renounce_ownership"))
+}
+
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    update: ConfigUpdate,
+) -> Result<Response, ContractError> {
+    ensure_role(deps.storage, &info.sender, Role::Admin)?;
+    ensure!(!update.is_empty(), ContractError::EmptyConfigUpdate {});
+    validate_config_update(deps.api, &update)?;
+
+    let mut attrs = vec![];
+    if let Some(ticket_price) = update.ticket_price {
+        attrs.push(Attribute::new("new_ticket_price", ticket_price.to_string()));
+    }
+    if let Some(fee_schedule) = &update.fee_schedule {
+        attrs.push(Attribute::new(
+            "reward_bps",
+            fee_schedule.reward_bps.to_string(),
+        ));
+        attrs.push(Attribute::new(
+            "admin_bps",
+            fee_schedule.admin_bps.to_string(),
+        ));
+        attrs.push(Attribute::new(
+            "reserve_bps",
+            fee_schedule.reserve_bps.to_string(),
+        ));
+        attrs.push(Attribute::new(
+            "ticket_fee_bps",
+            fee_schedule.ticket_fee_bps.to_string(),
+        ));
+    }
+    for fee_recipient in update.fee_recipients.iter().flatten() {
+        let destination = match &fee_recipient.destination {
+            FeeDestination::Address(address) => address.to_string(),
+            FeeDestination::Burn => "burn".to_string(),
+        };
+        attrs.push(Attribute::new(
+            "fee_recipient",
+            format!("{}:{}", destination, fee_recipient.weight_bps),
+        ));
+    }
+    if let Some(shoot_duration) = update.shoot_duration {
+        attrs.push(Attribute::new(
+            "new_shoot_duration",
+            shoot_duration.to_string(),
+        ));
+    }
+    if let Some(cooldown_policy) = &update.cooldown_policy {
+        attrs.push(Attribute::new(
+            "new_cooldown_policy",
+            format!("{:?}", cooldown_policy),
+        ));
+    }
+    if let Some(resolution_mode) = &update.resolution_mode {
+        attrs.push(Attribute::new(
+            "new_resolution_mode",
+            resolution_mode.as_str(),
+        ));
+    }
+    if let Some(goal_probability_bps) = update.goal_probability_bps {
+        attrs.push(Attribute::new(
+            "new_goal_probability_bps",
+            goal_probability_bps.to_string(),
+        ));
+    }
+    if let Some(refund_grace_period) = update.refund_grace_period {
+        attrs.push(Attribute::new(
+            "new_refund_grace_period",
+            refund_grace_period.to_string(),
+        ));
+    }
+    if let Some(liveness_period) = update.liveness_period {
+        attrs.push(Attribute::new(
+            "new_liveness_period",
+            liveness_period.to_string(),
+        ));
+    }
+    if let Some(config_timelock) = update.config_timelock {
+        attrs.push(Attribute::new(
+            "new_config_timelock",
+            config_timelock.to_string(),
+        ));
+    }
+
+    let res = Response::new()
+        .add_attribute("method", "execute_update_config")
+        .add_attributes(attrs);
+    schedule_config_change(deps.storage, &env, ConfigChange::Update(update), res)
+}
+
+// Nothing is applied unless every set field is valid
+fn validate_config_update(api: &dyn Api, update: &ConfigUpdate) -> Result<(), ContractError> {
+    if let Some(fee_schedule) = &update.fee_schedule {
+        validate_fee_schedule(fee_schedule)?;
+    }
+    if let Some(fee_recipients) = &update.fee_recipients {
+        validate_fee_recipients(api, fee_recipients)?;
+    }
+    if let Some(shoot_duration) = update.shoot_duration {
+        ensure!(
+            (MIN_SHOOT_DURATION..=MAX_SHOOT_DURATION).contains(&shoot_duration),
+            ContractError::InvalidShootDuration {
+                min: MIN_SHOOT_DURATION,
+                max: MAX_SHOOT_DURATION,
+                actual: shoot_duration,
+            }
+        );
+    }
+    if let Some(goal_probability_bps) = update.goal_probability_bps {
+        ensure!(
+            goal_probability_bps as u128 <= BPS_DENOMINATOR,
+            ContractError::InvalidGoalProbability {
+                goal_probability_bps
+            }
+        );
+    }
+    if let Some(refund_grace_period) = update.refund_grace_period {
+        ensure!(
+            refund_grace_period <= MAX_REFUND_GRACE_PERIOD,
+            ContractError::InvalidRefundGracePeriod {
+                max: MAX_REFUND_GRACE_PERIOD
+            }
+        );
+    }
+    // A shorter period would call the referee offline before a shot is even due
+    if let Some(liveness_period) = update.liveness_period {
+        ensure!(
+            liveness_period >= MAX_SHOOT_DURATION,
+            ContractError::InvalidLivenessPeriod {
+                min: MAX_SHOOT_DURATION
+            }
+        );
+    }
+    if let Some(config_timelock) = update.config_timelock {
+        ensure!(
+            config_timelock <= MAX_CONFIG_TIMELOCK,
+            ContractError::InvalidConfigTimelock {
+                max: MAX_CONFIG_TIMELOCK
+            }
+        );
+    }
+    Ok(())
+}
+
+fn validate_fee_recipients(
+    api: &dyn Api,
+    fee_recipients: &[FeeRecipient],
+) -> Result<(), ContractError> {
+    ensure!(
+        fee_recipients.len() as u32 <= MAX_FEE_RECIPIENTS,
+        ContractError::TooManyFeeRecipients {
+            max: MAX_FEE_RECIPIENTS
+        }
+    );
+    let total_bps: u32 = fee_recipients
+        .iter()
+        .map(|fee_recipient| fee_recipient.weight_bps as u32)
+        .sum();
+    ensure!(
+        total_bps == BPS_DENOMINATOR as u32,
+        ContractError::FeeRecipientWeights { total_bps }
+    );
+    for fee_recipient in fee_recipients.iter() {
+        if let FeeDestination::Address(address) = &fee_recipient.destination {
+            api.addr_validate(address.as_str())?;
+        }
+    }
+    Ok(())
+}
+
+fn validate_fee_schedule(fee_schedule: &FeeSchedule) -> Result<(), ContractError> {
+    let total_bps = fee_schedule.total_bps();
+    ensure!(
+        total_bps <= BPS_DENOMINATOR as u32,
+        ContractError::FeeScheduleTooHigh { total_bps }
+    );
+    ensure!(
+        fee_schedule.ticket_fee_bps as u128 <= BPS_DENOMINATOR,
+        ContractError::TicketFeeTooHigh {
+            ticket_fee_bps: fee_schedule.ticket_fee_bps
+        }
+    );
+    Ok(())
+}
+
+pub fn execute_set_drand_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_drand_config: DrandConfig,
+) -> Result<Response, ContractError> {
+    ensure_role(deps.storage, &info.sender, Role::Admin)?;
+    G2PubkeyRfc::from_variable(new_drand_config.pubkey.as_slice()).map_err(|err| {
+        ContractError::InvalidDrandConfig {
+            reason: err.to_string(),
+        }
+    })?;
+    ensure!(
+        new_drand_config.period > 0,
+        ContractError::InvalidDrandConfig {
+            reason: "period must be positive".to_string(),
+        }
+    );
+    DRAND_CONFIG.save(deps.storage, &new_drand_config)?;
+    Ok(Response::new()
+        .add_attribute("method", "execute_set_drand_config")
+        .add_attribute("pubkey", new_drand_config.pubkey.to_hex())
+        .add_attribute("genesis_time", new_drand_config.genesis_time.to_string())
+        .add_attribute("period", new_drand_config.period.to_string()))
+}
+
+pub fn execute_set_randomness_proxy(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_randomness_proxy: String,
+) -> Result<Response, ContractError> {
+    ensure_role(deps.storage, &info.sender, Role::Admin)?;
+    let proxy = deps.api.addr_validate(&new_randomness_proxy)?;
+    RANDOMNESS_PROXY.save(deps.storage, &proxy)?;
+    Ok(Response::new()
+        .add_attribute("method", "execute_set_randomness_proxy")
+        .add_attribute("new_randomness_proxy", proxy))
+}
+
+pub fn execute_set_attestation_key(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_attestation_key: Option<AttestationKey>,
+) -> Result<Response, ContractError> {
+    ensure_role(deps.storage, &info.sender, Role::Admin)?;
+    let key = match new_attestation_key {
+        Some(key) => key,
+        None => {
+            ATTESTATION_KEY.remove(deps.storage);
+            return Ok(Response::new()
+                .add_attribute("method", "execute_set_attestation_key")
+                .add_attribute("new_attestation_key", "none"));
+        }
+    };
+    let (key_type, valid) = match &key {
+        AttestationKey::Secp256k1(pubkey) => {
+            ("secp256k1", pubkey.len() == 33 || pubkey.len() == 65)
+        }
+        AttestationKey::Ed25519(pubkey) => ("ed25519", pubkey.len() == 32),
+    };
+    ensure!(valid, ContractError::InvalidAttestationKey {});
+    ATTESTATION_KEY.save(deps.storage, &key)?;
+    Ok(Response::new()
+        .add_attribute("method", "execute_set_attestation_key")
+        .add_attribute("new_attestation_key", key_type))
+}
+
+pub fn execute_grant_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+    role: Role,
+) -> Result<Response, ContractError> {
+    ensure_role(deps.storage, &info.sender, Role::Admin)?;
+    let address = deps.api.addr_validate(&address)?;
+    ensure!(
+        !ROLES.has(deps.storage, (role.as_str(), &address)),
+        ContractError::RoleAlreadyGranted {
+            address: address.to_string(),
+            role: role.as_str().to_string(),
+        }
+    );
+    ROLES.save(deps.storage, (role.as_str(), &address), &Empty {})?;
+    Ok(Response::new()
+        .add_event(
+            Event::new("role_granted")
+                .add_attribute("role", role.as_str())
+                .add_attribute("address", address.to_string()),
+        )
+        .add_attribute("method", "execute_grant_role")
+        .add_attribute("role", role.as_str())
+        .add_attribute("address", address))
+}
+
+pub fn execute_revoke_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+    role: Role,
+) -> Result<Response, ContractError> {
+    ensure_role(deps.storage, &info.sender, Role::Admin)?;
+    let address = deps.api.addr_validate(&address)?;
+    ensure!(
+        ROLES.has(deps.storage, (role.as_str(), &address)),
+        ContractError::RoleNotGranted {
+            address: address.to_string(),
+            role: role.as_str().to_string(),
+        }
+    );
+    if role == Role::Referee {
+        // The quorum must stay reachable
+        let threshold = REFEREE_THRESHOLD.load(deps.storage)?;
+        let referees = count_referees(deps.storage)? - 1;
+        ensure!(
+            threshold <= referees,
+            ContractError::InvalidRefereeThreshold {
+                threshold,
+                referees
+            }
+        );
+    }
+    ROLES.remove(deps.storage, (role.as_str(), &address));
+    Ok(Response::new()
+        .add_event(
+            Event::new("role_revoked")
+                .add_attribute("role", role.as_str())
+                .add_attribute("address", address.to_string()),
+        )
+        .add_attribute("method", "execute_revoke_role")
+        .add_attribute("role", role.as_str())
+        .add_attribute("address", address))
+}
+
+pub fn execute_set_referee_threshold(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    new_threshold: u32,
+) -> Result<Response, ContractError> {
+    ensure_role(deps.storage, &info.sender, Role::Admin)?;
+    validate_referee_threshold(deps.storage, new_threshold)?;
+    let res = Response::new()
+        .add_attribute("method", "execute_set_referee_threshold")
+        .add_attribute("new_threshold", new_threshold.to_string());
+    schedule_config_change(
+        deps.storage,
+        &env,
+        ConfigChange::RefereeThreshold(new_threshold),
+        res,
+    )
+}
+
+// Checked again when a queued threshold is applied, referees may have been revoked since
+fn validate_referee_threshold(storage: &dyn Storage, threshold: u32) -> Result<(), ContractError> {
+    let referees = count_referees(storage)?;
+    ensure!(
+        threshold > 0 && threshold <= referees,
+        ContractError::InvalidRefereeThreshold {
+            threshold,
+            referees
+        }
+    );
+    Ok(())
+}
+
+fn count_referees(storage: &dyn Storage) -> StdResult<u32> {
+    Ok(ROLES
+        .prefix(Role::Referee.as_str())
+        .keys(storage, None, None, Order::Ascending)
+        .count() as u32)
+}
+
+fn dispute_pending(storage: &dyn Storage) -> bool {
+    SETTLEMENTS
+        .range(storage, None, None, Order::Ascending)
+        .any(|item| item.map_or(true, |(_, settlement)| settlement.disputed))
+}
+
+// Disputed settlements, and settlements still inside their dispute window
+fn settlement_open(storage: &dyn Storage, now: u64) -> bool {
+    SETTLEMENTS
+        .range(storage, None, None, Order::Ascending)
+        .any(|item| {
+            item.map_or(true, |(_, settlement)| {
+                settlement.disputed || now < settlement.release_at
+            })
+        })
+}
+
+pub fn execute_set_dispute_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    new_dispute_config: Option<DisputeConfig>,
+) -> Result<Response, ContractError> {
+    ensure_role(deps.storage, &info.sender, Role::Admin)?;
+    let mut res = Response::new().add_attribute("method", "execute_set_dispute_config");
+    res = match &new_dispute_config {
+        Some(dispute_config) => {
+            deps.api.addr_validate(dispute_config.guardian.as_str())?;
+            ensure!(
+                dispute_config.period <= MAX_DISPUTE_PERIOD,
+                ContractError::InvalidDisputePeriod {
+                    max: MAX_DISPUTE_PERIOD
+                }
+            );
+            res.add_attribute("threshold", dispute_config.threshold.to_string())
+                .add_attribute("period", dispute_config.period.to_string())
+                .add_attribute("guardian", dispute_config.guardian.clone())
+        }
+        None => res.add_attribute("dispute_config", "none"),
+    };
+    schedule_config_change(
+        deps.storage,
+        &env,
+        ConfigChange::DisputeConfig(new_dispute_config),
+        res,
+    )
+}
+
+// Pending disputes need the guardian they were raised with to be resolved
+fn ensure_guardian_unlocked(
+    storage: &dyn Storage,
+    new_dispute_config: &Option<DisputeConfig>,
+) -> Result<(), ContractError> {
+    let guardian = DISPUTE_CONFIG
+        .may_load(storage)?
+        .map(|config| config.guardian);
+    let new_guardian = new_dispute_config
+        .as_ref()
+        .map(|config| config.guardian.clone());
+    ensure!(
+        guardian == new_guardian || !dispute_pending(storage),
+        ContractError::GuardianLocked {}
+    );
+    Ok(())
+}
+
+pub fn execute_set_bond_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    new_bond_config: BondConfig,
+) -> Result<Response, ContractError> {
+    ensure_role(deps.storage, &info.sender, Role::Admin)?;
+    ensure!(
+        new_bond_config.slash_bps as u128 <= BPS_DENOMINATOR,
+        ContractError::InvalidSlashShare {
+            slash_bps: new_bond_config.slash_bps
+        }
+    );
+    ensure!(
+        new_bond_config.unbonding_period <= MAX_UNBONDING_PERIOD,
+        ContractError::InvalidUnbondingPeriod {
+            max: MAX_UNBONDING_PERIOD
+        }
+    );
+    let res = Response::new()
+        .add_attribute("method", "execute_set_bond_config")
+        .add_attribute(
+            "unbonding_period",
+            new_bond_config.unbonding_period.to_string(),
+        )
+        .add_attribute("slash_bps", new_bond_config.slash_bps.to_string());
+    schedule_config_change(
+        deps.storage,
+        &env,
+        ConfigChange::BondConfig(new_bond_config),
+        res,
+    )
+}
+
+// Applies the change right away without a timelock, otherwise queues it
+fn schedule_config_change(
+    storage: &mut dyn Storage,
+    env: &Env,
+    change: ConfigChange,
+    res: Response,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(storage)?;
+    if config.config_timelock == 0 {
+        apply_config_change(storage, change)?;
+        return Ok(res);
+    }
+
+    let change_id = CONFIG_CHANGE_COUNT.load(storage)? + 1;
+    CONFIG_CHANGE_COUNT.save(storage, &change_id)?;
+    let queued_at = env.block.time.seconds();
+    let executable_at = queued_at + config.config_timelock;
+    // A stale change is not applied long after players stopped expecting it
+    let expires_at = executable_at + CONFIG_CHANGE_GRACE_PERIOD;
+    let event = Event::new("config_change_queued")
+        .add_attribute("change_id", change_id.to_string())
+        .add_attribute("change", change.as_str())
+        .add_attribute("executable_at", executable_at.to_string())
+        .add_attribute("expires_at", expires_at.to_string());
+    PENDING_CONFIG_CHANGES.save(
+        storage,
+        change_id,
+        &PendingConfigChange {
+            change,
+            queued_at,
+            executable_at,
+            expires_at,
+        },
+    )?;
+    Ok(res
+        .add_event(event)
+        .add_attribute("change_id", change_id.to_string()))
+}
+
+// Checks that depend on the state at the time the change takes effect are made here
+fn apply_config_change(
+    storage: &mut dyn Storage,
+    change: ConfigChange,
+) -> Result<(), ContractError> {
+    let mut config = CONFIG.load(storage)?;
+    match change {
+        ConfigChange::Update(update) => update.apply(&mut config),
+        ConfigChange::DisputeConfig(dispute_config) => {
+            ensure_guardian_unlocked(storage, &dispute_config)?;
+            match dispute_config {
+                Some(dispute_config) => DISPUTE_CONFIG.save(storage, &dispute_config)?,
+                None => DISPUTE_CONFIG.remove(storage),
+            }
+        }
+        ConfigChange::BondConfig(bond_config) => BOND_CONFIG.save(storage, &bond_config)?,
+        ConfigChange::RefereeThreshold(threshold) => {
+            validate_referee_threshold(storage, threshold)?;
+            REFEREE_THRESHOLD.save(storage, &threshold)?;
+        }
+    }
+    CONFIG.save(storage, &config)?;
+    Ok(())
+}
+
+pub fn execute_apply_config_change(
+    deps: DepsMut,
+    env: Env,
+    change_id: u64,
+) -> Result<Response, ContractError> {
+    let pending = PENDING_CONFIG_CHANGES
+        .may_load(deps.storage, change_id)?
+        .ok_or(ContractError::ConfigChangeNotFound { change_id })?;
+    let now = env.block.time.seconds();
+    ensure!(
+        now >= pending.executable_at,
+        ContractError::ConfigChangeNotReady {
+            change_id,
+            executable_at: pending.executable_at
+        }
+    );
+    ensure!(
+        now < pending.expires_at,
+        ContractError::ConfigChangeExpired {
+            change_id,
+            expires_at: pending.expires_at
+        }
+    );
+    let change = pending.change.as_str();
+    apply_config_change(deps.storage, pending.change)?;
+    PENDING_CONFIG_CHANGES.remove(deps.storage, change_id);
+    Ok(Response::new()
+        .add_event(
+            Event::new("config_change_applied")
+                .add_attribute("change_id", change_id.to_string())
+                .add_attribute("change", change),
+        )
+        .add_attribute("method", "apply_config_change"))
+}
+
+pub fn execute_cancel_config_change(
+    deps: DepsMut,
+    info: MessageInfo,
+    change_id: u64,
+) -> Result<Response, ContractError> {
+    ensure_role(deps.storage, &info.sender, Role::Admin)?;
+    ensure!(
+        PENDING_CONFIG_CHANGES.has(deps.storage, change_id),
+        ContractError::ConfigChangeNotFound { change_id }
+    );
+    PENDING_CONFIG_CHANGES.remove(deps.storage, change_id);
+    Ok(Response::new()
+        .add_event(
+            Event::new("config_change_cancelled").add_attribute("change_id", change_id.to_string()),
+        )
+        .add_attribute("method", "cancel_config_change"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    if let Some(amount) = PENDING_BURNS.may_load(deps.storage, msg.id)? {
+        return reply_burn(deps, msg, amount);
+    }
+    let (recipient, amount) = PENDING_PAYOUTS.load(deps.storage, msg.id)?;
+    PENDING_PAYOUTS.remove(deps.storage, msg.id);
+
+    let res = Response::new()
+        .add_attribute("method", "reply_payout")
+        .add_attribute("payout_id", msg.id.to_string());
+    match msg.result {
+        SubMsgResult::Ok(_) => Ok(res),
+        SubMsgResult::Err(err) => {
+            // Keep the failed transfer in escrow, the recipient can `Claim` it later
+            CLAIMABLE.update(deps.storage, &recipient, |claimable| -> StdResult<_> {
+                Ok(claimable.unwrap_or_default() + amount)
+            })?;
+            LEDGER.update(deps.storage, |mut ledger| -> StdResult<_> {
+                ledger.claimable += amount;
+                Ok(ledger)
+            })?;
+            Ok(res
+                .add_attribute("payout_escrowed", format!("{}:{}", recipient, amount))
+                .add_attribute("error", err))
+        }
+    }
+}
+
+// A failed burn must not revert the resolution, its share stays in the reserve instead
+fn reply_burn(deps: DepsMut, msg: Reply, amount: u128) -> Result<Response, ContractError> {
+    PENDING_BURNS.remove(deps.storage, msg.id);
+
+    let res = Response::new()
+        .add_attribute("method", "reply_burn")
+        .add_attribute("payout_id", msg.id.to_string());
+    match msg.result {
+        SubMsgResult::Ok(_) => Ok(res),
+        SubMsgResult::Err(err) => {
+            LEDGER.update(deps.storage, |mut ledger| -> StdResult<_> {
+                ledger.reserve += amount;
+                Ok(ledger)
+            })?;
+            Ok(res
+                .add_attribute("burn_escrowed", amount.to_string())
+                .add_attribute("error", err))
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::QueryConfig {} => to_json_binary(&query_config(deps)?),
+        QueryMsg::QueryShootDeadline { address } => {
+            to_json_binary(&query_shoot_deadline(deps, address)?)
+        }
+        QueryMsg::QueryBalance {} => to_json_binary(&query_balance(deps)?),
+        QueryMsg::QueryShot { shot_id } => to_json_binary(&query_shot(deps, shot_id)?),
+        QueryMsg::QueryLedger {} => to_json_binary(&query_ledger(deps, env)?),
+        QueryMsg::QueryClaimable { address } => to_json_binary(&query_claimable(deps, address)?),
+        QueryMsg::QueryFees {} => to_json_binary(&query_fees(deps)?),
+        QueryMsg::QuerySeedCommitment { commitment_id } => {
+            to_json_binary(&query_seed_commitment(deps, commitment_id)?)
+        }
+        QueryMsg::QueryDrandConfig {} => to_json_binary(&query_drand_config(deps)?),
+        QueryMsg::QueryRandomnessProxy {} => to_json_binary(&query_randomness_proxy(deps)?),
+        QueryMsg::QueryAttestationKey {} => to_json_binary(&query_attestation_key(deps)?),
+        QueryMsg::QueryRoles {} => to_json_binary(&query_roles(deps)?),
+        QueryMsg::QueryPauses {} => to_json_binary(&query_pauses(deps, env)?),
+        QueryMsg::QueryReferees {} => to_json_binary(&query_referees(deps)?),
+        QueryMsg::QueryGoalVotes { shot_id } => to_json_binary(&query_goal_votes(deps, shot_id)?),
+        QueryMsg::QueryDisputeConfig {} => to_json_binary(&query_dispute_config(deps)?),
+        QueryMsg::QuerySettlement { shot_id } => to_json_binary(&query_settlement(deps, shot_id)?),
+        QueryMsg::QueryBond {} => to_json_binary(&query_bond(deps)?),
+        QueryMsg::QuerySlashes { start_after, limit } => {
+            to_json_binary(&query_slashes(deps, start_after, limit)?)
+        }
+        QueryMsg::QueryLiveness {} => to_json_binary(&query_liveness(deps, env)?),
+        QueryMsg::QueryConfigChanges {} => to_json_binary(&query_config_changes(deps)?),
+        QueryMsg::QueryPendingOwner {} => to_json_binary(&query_pending_owner(deps)?),
+    }
+}
+
+fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner.map(|owner| owner.to_string()),
+        ticket_price: config.ticket_price,
+        fee_schedule: config.fee_schedule,
+        fee_recipients: config.fee_recipients,
+        shoot_duration: config.shoot_duration,
+        cooldown_policy: config.cooldown_policy,
+        resolution_mode: config.resolution_mode,
+        goal_probability_bps: config.goal_probability_bps,
+        refund_grace_period: config.refund_grace_period,
+        liveness_period: config.liveness_period,
+        config_timelock: config.config_timelock,
+    })
+}
+
+fn query_drand_config(deps: Deps) -> StdResult<DrandConfigResponse> {
+    let drand = DRAND_CONFIG.may_load(deps.storage)?;
+    Ok(DrandConfigResponse { drand })
+}
+
+fn query_randomness_proxy(deps: Deps) -> StdResult<RandomnessProxyResponse> {
+    let randomness_proxy = RANDOMNESS_PROXY.may_load(deps.storage)?;
+    Ok(RandomnessProxyResponse {
+        randomness_proxy: randomness_proxy.map(|proxy| proxy.to_string()),
+    })
+}
+
+fn query_attestation_key(deps: Deps) -> StdResult<AttestationKeyResponse> {
+    let attestation_key = ATTESTATION_KEY.may_load(deps.storage)?;
+    Ok(AttestationKeyResponse { attestation_key })
+}
+
+fn role_holders(storage: &dyn Storage, role: &Role) -> StdResult<Vec<String>> {
+    ROLES
+        .prefix(role.as_str())
+        .keys(storage, None, None, Order::Ascending)
+        .map(|address| address.map(|address| address.to_string()))
+        .collect()
+}
+
+fn query_config_changes(deps: Deps) -> StdResult<ConfigChangesResponse> {
+    let config_timelock = CONFIG.load(deps.storage)?.config_timelock;
+    let changes = PENDING_CONFIG_CHANGES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            item.map(|(change_id, pending)| ConfigChangeResponse {
+                change_id,
+                change: pending.change,
+                queued_at: pending.queued_at,
+                executable_at: pending.executable_at,
+                expires_at: pending.expires_at,
+            })
+        })
+        .collect::<StdResult<Vec<ConfigChangeResponse>>>()?;
+    Ok(ConfigChangesResponse {
+        config_timelock,
+        changes,
+    })
+}
+
+fn query_roles(deps: Deps) -> StdResult<RolesResponse> {
+    let owner = CONFIG.load(deps.storage)?.owner;
+    let roles = Role::ALL
+        .into_iter()
+        .map(|role| {
+            role_holders(deps.storage, &role).map(|addresses| RoleHolders { role, addresses })
+        })
+        .collect::<StdResult<Vec<RoleHolders>>>()?;
+    Ok(RolesResponse {
+        owner: owner.map(|owner| owner.to_string()),
+        roles,
+    })
+}
+
+fn query_pauses(deps: Deps, env: Env) -> StdResult<PausesResponse> {
+    let now = env.block.time.seconds();
+    let pauses = PauseTarget::ALL
+        .into_iter()
+        .map(|what| {
+            let pause = PAUSES
+                .may_load(deps.storage, what.as_str())?
+                .filter(|pause| pause.is_active(now));
+            Ok(PauseResponse {
+                what,
+                paused: pause.is_some(),
+                paused_at: pause.as_ref().map(|pause| pause.paused_at),
+                unpause_at: pause.and_then(|pause| pause.unpause_at),
+            })
+        })
+        .collect::<StdResult<Vec<PauseResponse>>>()?;
+    Ok(PausesResponse { pauses })
+}
+
+fn query_referees(deps: Deps) -> StdResult<RefereesResponse> {
+    let referees = role_holders(deps.storage, &Role::Referee)?;
+    let threshold = REFEREE_THRESHOLD.load(deps.storage)?;
+    Ok(RefereesResponse {
+        referees,
+        threshold,
+    })
+}
+
+fn query_goal_votes(deps: Deps, shot_id: u64) -> StdResult<GoalVotesResponse> {
+    let votes = GOAL_VOTES
+        .prefix(shot_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            item.map(|(referee, voted_at)| GoalVote {
+                referee: referee.to_string(),
+                voted_at,
+            })
+        })
+        .collect::<StdResult<Vec<GoalVote>>>()?;
+    Ok(GoalVotesResponse { shot_id, votes })
+}
+
+fn query_dispute_config(deps: Deps) -> StdResult<DisputeConfigResponse> {
+    let dispute_config = DISPUTE_CONFIG.may_load(deps.storage)?;
+    Ok(DisputeConfigResponse { dispute_config })
+}
+
+fn query_settlement(deps: Deps, shot_id: u64) -> StdResult<SettlementResponse> {
+    let settlement = SETTLEMENTS.may_load(deps.storage, shot_id)?;
+    Ok(SettlementResponse {
+        shot_id,
+        settlement,
+    })
+}
+
+fn query_bond(deps: Deps) -> StdResult<BondResponse> {
+    let bond = OPERATOR_BOND.load(deps.storage)?;
+    let bond_config = BOND_CONFIG.load(deps.storage)?;
+    Ok(BondResponse {
+        operator: bond.operator.map(|operator| operator.to_string()),
+        bonded: bond.bonded,
+        locked: bond.locked,
+        unbonding: bond.unbonding,
+        unbonding_release_at: bond.unbonding_release_at,
+        total_slashed: bond.total_slashed,
+        bond_config,
+    })
+}
+
+fn query_slashes(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<SlashesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_SLASH_LIMIT).min(MAX_SLASH_LIMIT) as usize;
+    let slashes = SLASHES
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| {
+            item.map(|(slash_id, record)| SlashResponse {
+                slash_id,
+                shot_id: record.shot_id,
+                amount: record.amount,
+                reason: record.reason,
+                timestamp: record.timestamp,
+            })
+        })
+        .collect::<StdResult<Vec<SlashResponse>>>()?;
+    Ok(SlashesResponse { slashes })
+}
+
+fn query_liveness(deps: Deps, env: Env) -> StdResult<LivenessResponse> {
+    let liveness_period = CONFIG.load(deps.storage)?.liveness_period;
+    let liveness = LIVENESS.load(deps.storage)?;
+    Ok(LivenessResponse {
+        degraded: liveness.is_degraded(env.block.time.seconds(), liveness_period),
+        awaiting_since: liveness.awaiting_since,
+        liveness_period,
+    })
+}
+
+fn query_pending_owner(deps: Deps) -> StdResult<PendingOwnerResponse> {
+    let pending_owner = PENDING_OWNER.may_load(deps.storage)?;
+    Ok(PendingOwnerResponse {
+        pending_owner: pending_owner
+            .as_ref()
+            .map(|pending_owner| pending_owner.address.to_string()),
+        expires_at: pending_owner.and_then(|pending_owner| pending_owner.expires_at),
+    })
+}
+
+fn query_shoot_deadline(deps: Deps, address: Addr) -> StdResult<ShootDeadlineResponse> {
+    let shoot_deadline = SHOOT_DEADLINE_MAPPER.load(deps.storage, address)?;
+    Ok(ShootDeadlineResponse { shoot_deadline })
+}
+
+fn query_shot(deps: Deps, shot_id: u64) -> StdResult<ShotResponse> {
+    let session = SHOT_SESSIONS.load(deps.storage, shot_id)?;
+    Ok(ShotResponse {
+        id: session.id,
+        player: session.player.to_string(),
+        paid_amount: session.paid_amount,
+        started_at: session.started_at,
+        duration: session.duration,
+        deadline: session.deadline,
+        status: session.status.clone(),
+        jackpot_basis: session.jackpot_basis,
+        reward_amount: session.reward_amount(),
+        resolution: session.resolution,
+    })
+}
+
+fn query_balance(deps: Deps) -> StdResult<ContractBalanceResponse> {
+    let ledger = LEDGER.load(deps.storage)?;
+    Ok(ContractBalanceResponse {
+        amount: ledger.jackpot,
+    })
+}
+
+fn query_ledger(deps: Deps, env: Env) -> StdResult<LedgerResponse> {
+    let ledger = LEDGER.load(deps.storage)?;
+    Ok(LedgerResponse {
+        jackpot: ledger.jackpot,
+        admin_fees: ledger.admin_fees,
+        reserve: ledger.reserve,
+        claimable: ledger.claimable,
+        settling: ledger.settling,
+        bond: ledger.bond,
+        admin_dust: ledger.admin_dust,
+        reserve_dust: ledger.reserve_dust,
+        unaccounted: query_unaccounted(deps, &env, &ledger)?,
+    })
+}
+
+fn query_seed_commitment(deps: Deps, commitment_id: u64) -> StdResult<SeedCommitmentResponse> {
+    let commitment = SEED_COMMITMENTS.load(deps.storage, commitment_id)?;
+    Ok(SeedCommitmentResponse {
+        commitment_id,
+        commitment: commitment.commitment,
+        shot_id: commitment.shot_id,
+        server_seed: commitment.server_seed,
+    })
+}
+
+fn query_fees(deps: Deps) -> StdResult<FeesResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let ledger = LEDGER.load(deps.storage)?;
+    Ok(FeesResponse {
+        ticket_fee_bps: config.fee_schedule.ticket_fee_bps,
+        available: ledger.admin_fees,
+        total_accrued: ledger.fees_accrued,
+        total_withdrawn: ledger.fees_withdrawn,
+    })
+}
+
+fn query_claimable(deps: Deps, address: Addr) -> StdResult<ClaimableResponse> {
+    let amount = CLAIMABLE
+        .may_load(deps.storage, &address)?
+        .unwrap_or_default();
+    Ok(ClaimableResponse { amount })
+}
+
+// Bank balance of `MAIN_DENOM` that is not tracked by the ledger
+fn query_unaccounted(deps: Deps, env: &Env, ledger: &Ledger) -> StdResult<u128> {
+    let main_denom = MAIN_DENOM.load(deps.storage)?;
+    let balance = deps
+        .querier
+        .query_balance(&env.contract.address, main_denom)?
+        .amount
+        .u128();
+    Ok(balance.saturating_sub(ledger.total()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::msg::RoleGrant;
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_dependencies_with_balances, mock_env, mock_info,
+    };
+    use cosmwasm_std::{attr, from_json, Addr, Coin, CosmosMsg, ReplyOn, Timestamp, Uint128};
+
+    fn owner_fee_recipients(owner: &str) -> Vec<FeeRecipient> {
+        vec![FeeRecipient {
+            destination: FeeDestination::Address(Addr::unchecked(owner)),
+            weight_bps: 10000,
+        }]
+    }
+
+    // The owner only implicitly holds the admin role, tests run the game from the owner address
+    fn operator_roles(address: &str) -> Option<Vec<RoleGrant>> {
+        Some(
+            [Role::Referee, Role::Treasury, Role::Pauser]
+                .into_iter()
+                .map(|role| RoleGrant {
+                    address: address.to_string(),
+                    role,
+                })
+                .collect(),
+        )
+    }
+
+    fn update_config(update: ConfigUpdate) -> ExecuteMsg {
+        ExecuteMsg::UpdateConfig {
+            ticket_price: update.ticket_price,
+            fee_schedule: update.fee_schedule,
+            fee_recipients: update.fee_recipients,
+            shoot_duration: update.shoot_duration,
+            cooldown_policy: update.cooldown_policy,
+            resolution_mode: update.resolution_mode,
+            goal_probability_bps: update.goal_probability_bps,
+            refund_grace_period: update.refund_grace_period,
+            liveness_period: update.liveness_period,
+            config_timelock: update.config_timelock,
+        }
+    }
+
+    fn default_fee_schedule() -> FeeSchedule {
+        FeeSchedule {
+            reward_bps: 8000,
+            admin_bps: 400,
+            reserve_bps: 0,
+            ticket_fee_bps: 0,
+        }
+    }
+
+    #[test]
+    fn test_proper_initialization() {
+        let mut deps = mock_dependencies();
+        //no owner specified in the instantiation message
+        let msg = InstantiateMsg {
+            owner: None,
+            main_denom: "usei".to_string(),
+            ..Default::default()
+        };
+        let env = mock_env();
+        let info = mock_info("creator", &[]);
+
+        let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // it worked, let's query the state
+        let state = CONFIG.load(&deps.storage).unwrap();
+        assert_eq!(
+            state,
+            Config {
+                owner: Some(Addr::unchecked("creator".to_string())),
+                ticket_price: 0,
+                fee_schedule: default_fee_schedule(),
+                fee_recipients: owner_fee_recipients("creator"),
+                shoot_duration: 90,
+                cooldown_policy: CooldownPolicy::AllowOverlap,
+                resolution_mode: ResolutionMode::Referee,
+                goal_probability_bps: DEFAULT_GOAL_PROBABILITY_BPS,
+                refund_grace_period: DEFAULT_REFUND_GRACE_PERIOD,
+                liveness_period: DEFAULT_LIVENESS_PERIOD,
+                config_timelock: 0,
+            }
+        );
+        //specifying an owner address in the instantiation message
+        let msg = InstantiateMsg {
+            owner: Some("specified_owner".to_string()),
+            main_denom: "usei".to_string(),
+            ..Default::default()
+        };
+
+        let res = instantiate(deps.as_mut(), env, info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // it worked, let's query the state
         let state = CONFIG.load(&deps.storage).unwrap();
         assert_eq!(
-            state,
-            Config {
-                owner: Addr::unchecked("specified_owner".to_string()),
-                ticket_price: 0,
-                reward_percentage: 80,
-                admin_percentage: 4,
-                shoot_duration: 90
+            state,
+            Config {
+                owner: Some(Addr::unchecked("specified_owner".to_string())),
+                ticket_price: 0,
+                fee_schedule: default_fee_schedule(),
+                fee_recipients: owner_fee_recipients("specified_owner"),
+                shoot_duration: 90,
+                cooldown_policy: CooldownPolicy::AllowOverlap,
+                resolution_mode: ResolutionMode::Referee,
+                goal_probability_bps: DEFAULT_GOAL_PROBABILITY_BPS,
+                refund_grace_period: DEFAULT_REFUND_GRACE_PERIOD,
+                liveness_period: DEFAULT_LIVENESS_PERIOD,
+                config_timelock: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_set_ticket_price() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("creator", &[]);
+        let msg = InstantiateMsg {
+            owner: None,
+            main_denom: "usei".to_string(),
+            ..Default::default()
+        };
+
+        let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        let msg = update_config(ConfigUpdate {
+            ticket_price: Some(100),
+            ..Default::default()
+        });
+
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                attr("method", "execute_update_config"),
+                attr("new_ticket_price", "100")
+            ]
+        );
+
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::QueryConfig {}).unwrap();
+        let config: Config = from_json(&res).unwrap();
+        assert_eq!(
+            Config {
+                owner: Some(Addr::unchecked("creator".to_string())),
+                ticket_price: 100,
+                fee_schedule: default_fee_schedule(),
+                fee_recipients: owner_fee_recipients("creator"),
+                shoot_duration: 90,
+                cooldown_policy: CooldownPolicy::AllowOverlap,
+                resolution_mode: ResolutionMode::Referee,
+                goal_probability_bps: DEFAULT_GOAL_PROBABILITY_BPS,
+                refund_grace_period: DEFAULT_REFUND_GRACE_PERIOD,
+                liveness_period: DEFAULT_LIVENESS_PERIOD,
+                config_timelock: 0,
+            },
+            config
+        );
+    }
+
+    #[test]
+    fn test_set_fee_schedule() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("creator", &[]);
+        let msg = InstantiateMsg {
+            owner: None,
+            main_denom: "usei".to_string(),
+            ..Default::default()
+        };
+
+        let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        let new_fee_schedule = FeeSchedule {
+            reward_bps: 9000,
+            admin_bps: 900,
+            reserve_bps: 100,
+            ticket_fee_bps: 0,
+        };
+        let msg = update_config(ConfigUpdate {
+            fee_schedule: Some(new_fee_schedule.clone()),
+            ..Default::default()
+        });
+
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                attr("method", "execute_update_config"),
+                attr("reward_bps", "9000"),
+                attr("admin_bps", "900"),
+                attr("reserve_bps", "100"),
+                attr("ticket_fee_bps", "0"),
+            ]
+        );
+
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::QueryConfig {}).unwrap();
+        let config: Config = from_json(&res).unwrap();
+        assert_eq!(
+            Config {
+                owner: Some(Addr::unchecked("creator".to_string())),
+                ticket_price: 0,
+                fee_schedule: new_fee_schedule,
+                fee_recipients: owner_fee_recipients("creator"),
+                shoot_duration: 90,
+                cooldown_policy: CooldownPolicy::AllowOverlap,
+                resolution_mode: ResolutionMode::Referee,
+                goal_probability_bps: DEFAULT_GOAL_PROBABILITY_BPS,
+                refund_grace_period: DEFAULT_REFUND_GRACE_PERIOD,
+                liveness_period: DEFAULT_LIVENESS_PERIOD,
+                config_timelock: 0,
+            },
+            config
+        );
+
+        // more than 100% in total
+        let msg = update_config(ConfigUpdate {
+            fee_schedule: Some(FeeSchedule {
+                reward_bps: 9000,
+                admin_bps: 900,
+                reserve_bps: 101,
+                ticket_fee_bps: 0,
+            }),
+            ..Default::default()
+        });
+        let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::FeeScheduleTooHigh { total_bps: 10001 }
+        ));
+    }
+
+    #[test]
+    fn test_shoot_for_free_twice_success() {
+        let env = mock_env();
+        let main_denom = "usei";
+        let mut deps = mock_dependencies_with_balances(&[(
+            env.contract.address.as_str(),
+            &[Coin::new(0, main_denom)],
+        )]);
+        let info = mock_info("creator", &[]);
+        let msg = InstantiateMsg {
+            owner: None,
+            main_denom: main_denom.to_string(),
+            ..Default::default()
+        };
+
+        let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        let ticket_price = CONFIG.load(deps.as_ref().storage).unwrap().ticket_price;
+        let msg = ExecuteMsg::Shoot {
+            client_seed: None,
+            kick: None,
+        };
+        let info_with_funds = mock_info(
+            "creator",
+            &[Coin {
+                denom: main_denom.to_string(),
+                amount: Uint128::from(ticket_price),
+            }],
+        );
+        let res = execute(deps.as_mut(), env.clone(), info_with_funds.clone(), msg).unwrap();
+
+        assert_eq!(
+            res.attributes,
+            vec![
+                attr("method", "execute_shoot"),
+                attr("shot_id", "1"),
+                attr(
+                    "shoot_deadline",
+                    env.block
+                        .time
+                        .seconds()
+                        .add(DEFAULT_SHOOT_DURATION)
+                        .to_string()
+                ),
+                attr("jackpot_basis", "0"),
+            ]
+        );
+
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::QueryShootDeadline {
+                address: Addr::unchecked("creator".to_string()),
+            },
+        )
+        .unwrap();
+        let shoot_deadline: u64 = from_json::<ShootDeadlineResponse>(&res)
+            .unwrap()
+            .shoot_deadline;
+        assert_eq!(
+            shoot_deadline,
+            env.block.time.seconds().add(DEFAULT_SHOOT_DURATION)
+        );
+    }
+
+    #[test]
+    fn test_shoot_10ntrn_success() {
+        let env = mock_env();
+        let main_denom = "usei";
+        let mut deps = mock_dependencies_with_balances(&[
+            (
+                env.contract.address.as_str(),
+                &[Coin::new(1000, main_denom)],
+            ),
+            ("player", &[Coin::new(100, main_denom)]),
+        ]);
+
+        let info = mock_info("creator", &[]);
+        let msg = InstantiateMsg {
+            owner: Some("creator".to_string()),
+            main_denom: main_denom.to_string(),
+            ..Default::default()
+        };
+
+        let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // set ticket price
+        let msg = update_config(ConfigUpdate {
+            ticket_price: Some(10),
+            ..Default::default()
+        });
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                attr("method", "execute_update_config"),
+                attr("new_ticket_price", "10")
+            ]
+        );
+
+        let ticket_price = CONFIG.load(deps.as_ref().storage).unwrap().ticket_price;
+        let info_with_funds = mock_info(
+            "player",
+            &[Coin {
+                denom: main_denom.to_string(),
+                amount: Uint128::from(ticket_price),
+            }],
+        );
+
+        // execute shoot
+        let msg = ExecuteMsg::Shoot {
+            client_seed: None,
+            kick: None,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info_with_funds.clone(), msg).unwrap();
+        // check response
+        assert_eq!(
+            res.attributes,
+            vec![
+                attr("method", "execute_shoot"),
+                attr("shot_id", "1"),
+                attr(
+                    "shoot_deadline",
+                    env.block
+                        .time
+                        .seconds()
+                        .add(DEFAULT_SHOOT_DURATION)
+                        .to_string()
+                ),
+                attr("jackpot_basis", "10"),
+            ]
+        );
+
+        // check if deadline is set
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::QueryShootDeadline {
+                address: Addr::unchecked("player".to_string()),
+            },
+        )
+        .unwrap();
+        let shoot_deadline: u64 = from_json::<ShootDeadlineResponse>(&res)
+            .unwrap()
+            .shoot_deadline;
+        assert_eq!(
+            shoot_deadline,
+            env.block.time.seconds().add(DEFAULT_SHOOT_DURATION)
+        );
+    }
+
+    #[test]
+    fn test_shoot_10ntrn_and_goal_shot_success() {
+        let mut env = mock_env();
+        let main_denom = "usei";
+        let mut deps = mock_dependencies_with_balances(&[
+            (env.contract.address.as_str(), &[Coin::new(100, main_denom)]),
+            ("player", &[Coin::new(100, main_denom)]),
+        ]);
+
+        let info = mock_info("creator", &[]);
+        let msg = InstantiateMsg {
+            owner: Some("creator".to_string()),
+            main_denom: main_denom.to_string(),
+            roles: operator_roles("creator"),
+            ..Default::default()
+        };
+
+        let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // set ticket price
+        let msg = update_config(ConfigUpdate {
+            ticket_price: Some(100),
+            ..Default::default()
+        });
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                attr("method", "execute_update_config"),
+                attr("new_ticket_price", "100")
+            ]
+        );
+
+        let ticket_price = CONFIG.load(deps.as_ref().storage).unwrap().ticket_price;
+        let info_with_funds = mock_info(
+            "player",
+            &[Coin {
+                denom: main_denom.to_string(),
+                amount: Uint128::from(ticket_price),
+            }],
+        );
+
+        // execute shoot
+        let msg = ExecuteMsg::Shoot {
+            client_seed: None,
+            kick: None,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info_with_funds.clone(), msg).unwrap();
+        // check response
+        assert_eq!(
+            res.attributes,
+            vec![
+                attr("method", "execute_shoot"),
+                attr("shot_id", "1"),
+                attr(
+                    "shoot_deadline",
+                    env.block
+                        .time
+                        .seconds()
+                        .add(DEFAULT_SHOOT_DURATION)
+                        .to_string()
+                ),
+                attr("jackpot_basis", "100"),
+            ]
+        );
+
+        // check if deadline is set
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::QueryShootDeadline {
+                address: Addr::unchecked("player".to_string()),
+            },
+        )
+        .unwrap();
+        let shoot_deadline: u64 = from_json::<ShootDeadlineResponse>(&res)
+            .unwrap()
+            .shoot_deadline;
+        assert_eq!(
+            shoot_deadline,
+            env.block.time.seconds().add(DEFAULT_SHOOT_DURATION)
+        );
+
+        env.block.time = Timestamp::from_seconds(env.block.time.seconds() + 50);
+
+        // calculate reward should be 80% of the contract balance
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::QueryBalance {}).unwrap();
+        let contract_balance: ContractBalanceResponse = from_json(&res).unwrap();
+        let reward_amount = contract_balance.amount * 80 / 100;
+        let admin_amount = contract_balance.amount * 4 / 100;
+
+        // goal shot
+        let msg = ExecuteMsg::GoalShot {
+            shot_id: 1,
+            attestation: None,
+        };
+        let info = mock_info("creator", &[]);
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // check response
+        assert_eq!(
+            res.attributes,
+            vec![
+                attr("method", "goal_shot"),
+                attr("shot_id", "1"),
+                attr("pre_balance", contract_balance.amount.to_string()),
+                attr("jackpot_basis", contract_balance.amount.to_string()),
+                attr("reward_transfer_to_admin", admin_amount.to_string()),
+                attr("fee_transfer", format!("creator:{}", admin_amount)),
+                attr("reward_claimable", reward_amount.to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_goal_shot_twice_fails() {
+        let env = mock_env();
+        let main_denom = "usei";
+        let mut deps = mock_dependencies_with_balances(&[(
+            env.contract.address.as_str(),
+            &[Coin::new(100, main_denom)],
+        )]);
+
+        let info = mock_info("creator", &[]);
+        let msg = InstantiateMsg {
+            owner: None,
+            main_denom: main_denom.to_string(),
+            roles: operator_roles("creator"),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // move the pre-funded balance into the jackpot
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::SyncBalance {
+                bucket: PoolBucket::Jackpot,
+            },
+        )
+        .unwrap();
+
+        // free shot by player
+        let player_info = mock_info("player", &[Coin::new(0, main_denom)]);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            player_info,
+            ExecuteMsg::Shoot {
+                client_seed: None,
+                kick: None,
+            },
+        )
+        .unwrap();
+
+        // unknown shot id
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::GoalShot {
+                shot_id: 2,
+                attestation: None,
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::ShotNotFound { shot_id: 2 }));
+
+        // first goal pays out
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::GoalShot {
+                shot_id: 1,
+                attestation: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(1, res.messages.len());
+
+        // the session is consumed
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            ExecuteMsg::GoalShot {
+                shot_id: 1,
+                attestation: None,
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::ShotAlreadyResolved { shot_id: 1, .. }
+        ));
+
+        let res = query(deps.as_ref(), env, QueryMsg::QueryShot { shot_id: 1 }).unwrap();
+        let shot: ShotResponse = from_json(&res).unwrap();
+        assert_eq!(shot.player, "player");
+        assert_eq!(shot.status, ShotStatus::Goal);
+    }
+
+    #[test]
+    fn test_miss_and_expire_shots() {
+        let mut env = mock_env();
+        let main_denom = "usei";
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("creator", &[]);
+        let msg = InstantiateMsg {
+            owner: None,
+            main_denom: main_denom.to_string(),
+            roles: operator_roles("creator"),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let player_info = mock_info("player", &[Coin::new(0, main_denom)]);
+        for _ in 0..3 {
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                player_info.clone(),
+                ExecuteMsg::Shoot {
+                    client_seed: None,
+                    kick: None,
+                },
+            )
+            .unwrap();
+        }
+
+        // only a referee records a miss
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            player_info,
+            ExecuteMsg::MissShot {
+                shot_id: 1,
+                attestation: None,
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::MissingRole { role } if role == "referee"));
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::MissShot {
+                shot_id: 1,
+                attestation: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(res.events.len(), 1);
+        assert_eq!(res.events[0].ty, "shot_status");
+        assert!(res.events[0]
+            .attributes
+            .contains(&attr("status", ShotStatus::Miss.as_str())));
+
+        // nothing to expire before the deadline
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::ExpireShots { limit: None },
+        )
+        .unwrap();
+        assert!(res.attributes.contains(&attr("expired_count", "0")));
+
+        env.block.time = Timestamp::from_seconds(env.block.time.seconds() + DEFAULT_SHOOT_DURATION);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::ExpireShots { limit: Some(1) },
+        )
+        .unwrap();
+        assert!(res.attributes.contains(&attr("expired_count", "1")));
+        assert!(res.events[0].attributes.contains(&attr("shot_id", "2")));
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::ExpireShots { limit: None },
+        )
+        .unwrap();
+        assert!(res.attributes.contains(&attr("expired_count", "1")));
+
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::QueryShot { shot_id: 1 },
+        )
+        .unwrap();
+        assert_eq!(
+            from_json::<ShotResponse>(&res).unwrap().status,
+            ShotStatus::Miss
+        );
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::QueryShot { shot_id: 3 },
+        )
+        .unwrap();
+        assert_eq!(
+            from_json::<ShotResponse>(&res).unwrap().status,
+            ShotStatus::Expired
+        );
+
+        // a resolved shot cannot be resolved again
+        let err = execute(
+            deps.as_mut(),
+            env,
+            info,
+            ExecuteMsg::MissShot {
+                shot_id: 3,
+                attestation: None,
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::ShotAlreadyResolved { shot_id: 3, .. }
+        ));
+    }
+
+    #[test]
+    fn test_set_shoot_duration() {
+        let mut env = mock_env();
+        let main_denom = "usei";
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("creator", &[]);
+        let msg = InstantiateMsg {
+            owner: None,
+            main_denom: main_denom.to_string(),
+            roles: operator_roles("creator"),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // out of bounds
+        for new_shoot_duration in [MIN_SHOOT_DURATION - 1, MAX_SHOOT_DURATION + 1] {
+            let err = execute(
+                deps.as_mut(),
+                env.clone(),
+                info.clone(),
+                update_config(ConfigUpdate {
+                    shoot_duration: Some(new_shoot_duration),
+                    ..Default::default()
+                }),
+            )
+            .unwrap_err();
+            assert!(matches!(err, ContractError::InvalidShootDuration { .. }));
+        }
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            update_config(ConfigUpdate {
+                shoot_duration: Some(300),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+
+        let player_info = mock_info("player", &[Coin::new(0, main_denom)]);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            player_info,
+            ExecuteMsg::Shoot {
+                client_seed: None,
+                kick: None,
+            },
+        )
+        .unwrap();
+        let shoot_deadline = env.block.time.seconds() + 300;
+        assert!(res
+            .attributes
+            .contains(&attr("shoot_deadline", shoot_deadline.to_string())));
+
+        // shortening the duration does not affect the shot in progress
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            update_config(ConfigUpdate {
+                shoot_duration: Some(60),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+        env.block.time = Timestamp::from_seconds(env.block.time.seconds() + 120);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            ExecuteMsg::GoalShot {
+                shot_id: 1,
+                attestation: None,
+            },
+        )
+        .unwrap();
+
+        let res = query(deps.as_ref(), env, QueryMsg::QueryShot { shot_id: 1 }).unwrap();
+        let shot: ShotResponse = from_json(&res).unwrap();
+        assert_eq!(shot.duration, 300);
+        assert_eq!(shot.deadline, shoot_deadline);
+    }
+
+    #[test]
+    fn test_cooldown_policy() {
+        let mut env = mock_env();
+        let main_denom = "usei";
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("creator", &[]);
+        let msg = InstantiateMsg {
+            owner: None,
+            main_denom: main_denom.to_string(),
+            roles: operator_roles("creator"),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        let player_info = mock_info("player", &[Coin::new(0, main_denom)]);
+        let start = env.block.time.seconds();
+
+        // overlapping shots are allowed by default
+        for _ in 0..2 {
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                player_info.clone(),
+                ExecuteMsg::Shoot {
+                    client_seed: None,
+                    kick: None,
+                },
+            )
+            .unwrap();
+        }
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            update_config(ConfigUpdate {
+                cooldown_policy: Some(CooldownPolicy::UntilResolved),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            player_info.clone(),
+            ExecuteMsg::Shoot {
+                client_seed: None,
+                kick: None,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            ContractError::ShootCooldownActive {
+                available_at: start + DEFAULT_SHOOT_DURATION
+            }
+            .to_string()
+        );
+
+        // resolving the last shot releases the player
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::MissShot {
+                shot_id: 2,
+                attestation: None,
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            player_info.clone(),
+            ExecuteMsg::Shoot {
+                client_seed: None,
+                kick: None,
+            },
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            update_config(ConfigUpdate {
+                cooldown_policy: Some(CooldownPolicy::Fixed { seconds: 30 }),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+        env.block.time = Timestamp::from_seconds(start + 29);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            player_info.clone(),
+            ExecuteMsg::Shoot {
+                client_seed: None,
+                kick: None,
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::ShootCooldownActive { available_at } if available_at == start + 30
+        ));
+
+        env.block.time = Timestamp::from_seconds(start + 30);
+        execute(
+            deps.as_mut(),
+            env,
+            player_info,
+            ExecuteMsg::Shoot {
+                client_seed: None,
+                kick: None,
+            },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_jackpot_locked_at_shoot_and_rebased() {
+        let env = mock_env();
+        let main_denom = "usei";
+        let mut deps = mock_dependencies_with_balances(&[(
+            env.contract.address.as_str(),
+            &[Coin::new(1000, main_denom)],
+        )]);
+
+        let info = mock_info("creator", &[]);
+        let msg = InstantiateMsg {
+            owner: None,
+            main_denom: main_denom.to_string(),
+            roles: operator_roles("creator"),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // move the pre-funded balance into the jackpot
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::SyncBalance {
+                bucket: PoolBucket::Jackpot,
+            },
+        )
+        .unwrap();
+
+        let player_info = mock_info("player", &[Coin::new(0, main_denom)]);
+        let other_info = mock_info("other", &[Coin::new(0, main_denom)]);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            player_info,
+            ExecuteMsg::Shoot {
+                client_seed: None,
+                kick: None,
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            other_info,
+            ExecuteMsg::Shoot {
+                client_seed: None,
+                kick: None,
+            },
+        )
+        .unwrap();
+
+        // later fee changes don't affect the prize shown at shoot time
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            update_config(ConfigUpdate {
+                fee_schedule: Some(FeeSchedule {
+                    reward_bps: 1000,
+                    admin_bps: 400,
+                    reserve_bps: 0,
+                    ticket_fee_bps: 0,
+                }),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::QueryShot { shot_id: 2 },
+        )
+        .unwrap();
+        let shot: ShotResponse = from_json(&res).unwrap();
+        assert_eq!(shot.jackpot_basis, 1000);
+        assert_eq!(shot.reward_amount, 800);
+
+        // first goal wins, the other pending shot is re-based on what is left
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::GoalShot {
+                shot_id: 1,
+                attestation: None,
+            },
+        )
+        .unwrap();
+        assert!(res.attributes.contains(&attr("reward_claimable", "800")));
+        let rebased = res.events.iter().find(|e| e.ty == "shot_rebased").unwrap();
+        assert!(rebased.attributes.contains(&attr("shot_id", "2")));
+        assert!(rebased.attributes.contains(&attr("jackpot_basis", "160")));
+
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::QueryShot { shot_id: 2 },
+        )
+        .unwrap();
+        let shot: ShotResponse = from_json(&res).unwrap();
+        assert_eq!(shot.jackpot_basis, 160);
+        assert_eq!(shot.reward_amount, 128);
+
+        let res = execute(
+            deps.as_mut(),
+            env,
+            info,
+            ExecuteMsg::GoalShot {
+                shot_id: 2,
+                attestation: None,
+            },
+        )
+        .unwrap();
+        assert!(res.attributes.contains(&attr("reward_claimable", "128")));
+    }
+
+    #[test]
+    fn test_ledger_tracks_tickets_and_sync_balance() {
+        let env = mock_env();
+        let main_denom = "usei";
+        // 500 was donated to the contract, the ticket funds are not part of the mocked balance
+        let mut deps = mock_dependencies_with_balances(&[(
+            env.contract.address.as_str(),
+            &[Coin::new(500, main_denom)],
+        )]);
+
+        let info = mock_info("creator", &[]);
+        let msg = InstantiateMsg {
+            owner: None,
+            main_denom: main_denom.to_string(),
+            roles: operator_roles("creator"),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            update_config(ConfigUpdate {
+                ticket_price: Some(100),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+
+        let player_info = mock_info("player", &[Coin::new(100, main_denom)]);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            player_info,
+            ExecuteMsg::Shoot {
+                client_seed: None,
+                kick: None,
+            },
+        )
+        .unwrap();
+        assert!(res.attributes.contains(&attr("jackpot_basis", "100")));
+
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::QueryLedger {}).unwrap();
+        assert_eq!(
+            from_json::<LedgerResponse>(&res).unwrap(),
+            LedgerResponse {
+                jackpot: 100,
+                admin_fees: 0,
+                reserve: 0,
+                claimable: 0,
+                settling: 0,
+                bond: 0,
+                admin_dust: 0,
+                reserve_dust: 0,
+                unaccounted: 400,
+            }
+        );
+
+        // only the treasury can sweep unaccounted funds
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("player", &[]),
+            ExecuteMsg::SyncBalance {
+                bucket: PoolBucket::Jackpot,
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::MissingRole { role } if role == "treasury"));
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            ExecuteMsg::SyncBalance {
+                bucket: PoolBucket::Reserve,
+            },
+        )
+        .unwrap();
+        assert!(res.attributes.contains(&attr("amount", "400")));
+
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::QueryLedger {}).unwrap();
+        let ledger: LedgerResponse = from_json(&res).unwrap();
+        assert_eq!(ledger.reserve, 400);
+        assert_eq!(ledger.unaccounted, 0);
+
+        // the jackpot excludes the reserve
+        let res = query(deps.as_ref(), env, QueryMsg::QueryBalance {}).unwrap();
+        assert_eq!(
+            from_json::<ContractBalanceResponse>(&res).unwrap().amount,
+            100
+        );
+    }
+
+    #[test]
+    fn test_fee_dust_carried_forward() {
+        let env = mock_env();
+        let main_denom = "usei";
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("creator", &[]);
+        let msg = InstantiateMsg {
+            owner: None,
+            main_denom: main_denom.to_string(),
+            roles: operator_roles("creator"),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            update_config(ConfigUpdate {
+                ticket_price: Some(3),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            update_config(ConfigUpdate {
+                fee_schedule: Some(FeeSchedule {
+                    reward_bps: 0,
+                    admin_bps: 2500,
+                    reserve_bps: 0,
+                    ticket_fee_bps: 0,
+                }),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+        let player_info = mock_info("player", &[Coin::new(3, main_denom)]);
+
+        // 25% of 3 rounds down to 0, the remainder is kept as dust
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            player_info.clone(),
+            ExecuteMsg::Shoot {
+                client_seed: None,
+                kick: None,
+            },
+        )
+        .unwrap();
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::GoalShot {
+                shot_id: 1,
+                attestation: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 0);
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::QueryLedger {}).unwrap();
+        let ledger: LedgerResponse = from_json(&res).unwrap();
+        assert_eq!(ledger.jackpot, 3);
+        assert_eq!(ledger.admin_dust, 7500);
+
+        // 25% of 6 is 1.5, plus 0.75 of dust
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            player_info,
+            ExecuteMsg::Shoot {
+                client_seed: None,
+                kick: None,
+            },
+        )
+        .unwrap();
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            ExecuteMsg::GoalShot {
+                shot_id: 2,
+                attestation: None,
+            },
+        )
+        .unwrap();
+        assert!(res
+            .attributes
+            .contains(&attr("reward_transfer_to_admin", "2")));
+        let res = query(deps.as_ref(), env, QueryMsg::QueryLedger {}).unwrap();
+        let ledger: LedgerResponse = from_json(&res).unwrap();
+        assert_eq!(ledger.jackpot, 4);
+        assert_eq!(ledger.admin_dust, 2500);
+    }
+
+    #[test]
+    fn test_fee_recipients_split() {
+        let env = mock_env();
+        let main_denom = "usei";
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("creator", &[]);
+        let msg = InstantiateMsg {
+            owner: None,
+            main_denom: main_denom.to_string(),
+            roles: operator_roles("creator"),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // weights must add up to 100%
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            update_config(ConfigUpdate {
+                fee_recipients: Some(vec![FeeRecipient {
+                    destination: FeeDestination::Burn,
+                    weight_bps: 9999,
+                }]),
+                ..Default::default()
+            }),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::FeeRecipientWeights { total_bps: 9999 }
+        ));
+
+        let new_fee_recipients = vec![
+            FeeRecipient {
+                destination: FeeDestination::Address(Addr::unchecked("treasury")),
+                weight_bps: 5000,
+            },
+            FeeRecipient {
+                destination: FeeDestination::Address(Addr::unchecked("devfund")),
+                weight_bps: 2000,
+            },
+            FeeRecipient {
+                destination: FeeDestination::Burn,
+                weight_bps: 2000,
+            },
+            FeeRecipient {
+                destination: FeeDestination::Address(Addr::unchecked("charity")),
+                weight_bps: 1000,
+            },
+        ];
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            update_config(ConfigUpdate {
+                fee_recipients: Some(new_fee_recipients),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            update_config(ConfigUpdate {
+                ticket_price: Some(2500),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+
+        // admin fee is 4% of 2500 = 100
+        let player_info = mock_info("player", &[Coin::new(2500, main_denom)]);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            player_info,
+            ExecuteMsg::Shoot {
+                client_seed: None,
+                kick: None,
+            },
+        )
+        .unwrap();
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            ExecuteMsg::GoalShot {
+                shot_id: 1,
+                attestation: None,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(res.messages.len(), 4);
+        assert!(res
+            .attributes
+            .contains(&attr("fee_transfer", "treasury:50")));
+        assert!(res.attributes.contains(&attr("fee_transfer", "devfund:20")));
+        assert!(res.attributes.contains(&attr("fee_burn", "20")));
+        assert!(res.attributes.contains(&attr("fee_transfer", "charity:10")));
+        let burn_msg = res.messages[2].clone();
+        assert_eq!(
+            burn_msg.msg,
+            BankMsg::Burn {
+                amount: vec![Coin::new(20, main_denom)],
+            }
+            .into()
+        );
+
+        // a failed burn keeps its share in the reserve instead of reverting the goal
+        assert_eq!(burn_msg.reply_on, ReplyOn::Always);
+        let res = reply(
+            deps.as_mut(),
+            env.clone(),
+            Reply {
+                id: burn_msg.id,
+                result: SubMsgResult::Err("burn disabled".to_string()),
+            },
+        )
+        .unwrap();
+        assert!(res.attributes.contains(&attr("burn_escrowed", "20")));
+        let res = query(deps.as_ref(), env, QueryMsg::QueryLedger {}).unwrap();
+        assert_eq!(from_json::<LedgerResponse>(&res).unwrap().reserve, 20);
+    }
+
+    #[test]
+    fn test_release_reserve() {
+        let env = mock_env();
+        let main_denom = "usei";
+        let mut deps = mock_dependencies_with_balances(&[(
+            env.contract.address.as_str(),
+            &[Coin::new(100, main_denom)],
+        )]);
+        let info = mock_info("creator", &[]);
+        let msg = InstantiateMsg {
+            owner: None,
+            main_denom: main_denom.to_string(),
+            roles: operator_roles("creator"),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::SyncBalance {
+                bucket: PoolBucket::Reserve,
+            },
+        )
+        .unwrap();
+
+        let release = |amount: u128, recipient: Option<&str>| ExecuteMsg::ReleaseReserve {
+            amount,
+            recipient: recipient.map(str::to_string),
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            release(10, None),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::MissingRole { role } if role == "treasury"));
+        let err =
+            execute(deps.as_mut(), env.clone(), info.clone(), release(101, None)).unwrap_err();
+        assert!(matches!(err, ContractError::InsufficientBalance {}));
+
+        // part of the reserve goes back to the players, part is paid out
+        execute(deps.as_mut(), env.clone(), info.clone(), release(60, None)).unwrap();
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            release(30, Some("treasury")),
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "treasury".to_string(),
+                amount: vec![Coin::new(30, main_denom)],
+            })
+        );
+        let res = query(deps.as_ref(), env, QueryMsg::QueryLedger {}).unwrap();
+        let ledger: LedgerResponse = from_json(&res).unwrap();
+        assert_eq!((ledger.jackpot, ledger.reserve), (60, 10));
+    }
+
+    #[test]
+    fn test_claim_prize_and_escrow_failed_fee_transfer() {
+        let env = mock_env();
+        let main_denom = "usei";
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("creator", &[]);
+        let msg = InstantiateMsg {
+            owner: None,
+            main_denom: main_denom.to_string(),
+            roles: operator_roles("creator"),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            update_config(ConfigUpdate {
+                ticket_price: Some(100),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+
+        let player_info = mock_info("player", &[Coin::new(100, main_denom)]);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            player_info.clone(),
+            ExecuteMsg::Shoot {
+                client_seed: None,
+                kick: None,
+            },
+        )
+        .unwrap();
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            ExecuteMsg::GoalShot {
+                shot_id: 1,
+                attestation: None,
+            },
+        )
+        .unwrap();
+
+        // the fee transfer to the owner fails, it is kept in escrow
+        let fee_msg = res.messages[0].clone();
+        assert_eq!(fee_msg.reply_on, ReplyOn::Always);
+        reply(
+            deps.as_mut(),
+            env.clone(),
+            Reply {
+                id: fee_msg.id,
+                result: SubMsgResult::Err("blocked address".to_string()),
+            },
+        )
+        .unwrap();
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::QueryClaimable {
+                address: Addr::unchecked("creator"),
+            },
+        )
+        .unwrap();
+        assert_eq!(from_json::<ClaimableResponse>(&res).unwrap().amount, 4);
+
+        // the winner pulls the prize
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            player_info.clone(),
+            ExecuteMsg::Claim {},
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            BankMsg::Send {
+                to_address: "player".to_string(),
+                amount: vec![Coin::new(80, main_denom)],
+            }
+            .into()
+        );
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            player_info,
+            ExecuteMsg::Claim {},
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::NothingToClaim {}));
+
+        let res = query(deps.as_ref(), env, QueryMsg::QueryLedger {}).unwrap();
+        let ledger: LedgerResponse = from_json(&res).unwrap();
+        assert_eq!(ledger.jackpot, 16);
+        assert_eq!(ledger.claimable, 4);
+    }
+
+    #[test]
+    fn test_ticket_fee_accrual_and_withdraw() {
+        let env = mock_env();
+        let main_denom = "usei";
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("creator", &[]);
+        let msg = InstantiateMsg {
+            owner: None,
+            main_denom: main_denom.to_string(),
+            roles: operator_roles("creator"),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            update_config(ConfigUpdate {
+                ticket_price: Some(100),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            update_config(ConfigUpdate {
+                fee_schedule: Some(FeeSchedule {
+                    reward_bps: 8000,
+                    admin_bps: 0,
+                    reserve_bps: 0,
+                    ticket_fee_bps: 500,
+                }),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+
+        let player_info = mock_info("player", &[Coin::new(100, main_denom)]);
+        for _ in 0..2 {
+            let res = execute(
+                deps.as_mut(),
+                env.clone(),
+                player_info.clone(),
+                ExecuteMsg::Shoot {
+                    client_seed: None,
+                    kick: None,
+                },
+            )
+            .unwrap();
+            assert!(res.attributes.contains(&attr("ticket_fee", "5")));
+        }
+
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::QueryBalance {}).unwrap();
+        assert_eq!(
+            from_json::<ContractBalanceResponse>(&res).unwrap().amount,
+            190
+        );
+
+        // cannot withdraw more than accrued
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::WithdrawFees {
+                amount: 11,
+                recipient: None,
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InsufficientBalance {}));
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            ExecuteMsg::WithdrawFees {
+                amount: 6,
+                recipient: Some("treasury".to_string()),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            BankMsg::Send {
+                to_address: "treasury".to_string(),
+                amount: vec![Coin::new(6, main_denom)],
+            }
+            .into()
+        );
+
+        let res = query(deps.as_ref(), env, QueryMsg::QueryFees {}).unwrap();
+        assert_eq!(
+            from_json::<FeesResponse>(&res).unwrap(),
+            FeesResponse {
+                ticket_fee_bps: 500,
+                available: 4,
+                total_accrued: 10,
+                total_withdrawn: 6,
+            }
+        );
+    }
+
+    #[test]
+    fn test_commit_reveal_shot() {
+        let mut env = mock_env();
+        // shots are bound to drand round 123
+        env.block.time = Timestamp::from_seconds(QUICKNET_GENESIS + 121 * 3 + 1);
+        let main_denom = "usei";
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("creator", &[]);
+        let msg = InstantiateMsg {
+            owner: None,
+            main_denom: main_denom.to_string(),
+            roles: operator_roles("creator"),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            update_config(ConfigUpdate {
+                resolution_mode: Some(ResolutionMode::CommitReveal),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::SetDrandConfig {
+                new_drand_config: DrandConfig {
+                    pubkey: HexBinary::from_hex(QUICKNET_PUBKEY).unwrap(),
+                    genesis_time: QUICKNET_GENESIS,
+                    period: 3,
+                },
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            update_config(ConfigUpdate {
+                goal_probability_bps: Some(10000),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+        let player_info = mock_info("player", &[Coin::new(0, main_denom)]);
+        let shoot = ExecuteMsg::Shoot {
+            client_seed: Some("lucky".to_string()),
+            kick: None,
+        };
+
+        // the player seed is required
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            player_info.clone(),
+            ExecuteMsg::Shoot {
+                client_seed: None,
+                kick: None,
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidClientSeed { .. }));
+
+        // and so is an operator commitment
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            player_info.clone(),
+            shoot.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::NoSeedCommitment {}));
+
+        let seed_a = HexBinary::from(vec![1u8; 32]);
+        let seed_b = HexBinary::from(vec![2u8; 32]);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::CommitSeeds {
+                commitments: vec![
+                    HexBinary::from(sha256(seed_a.as_slice())),
+                    HexBinary::from(sha256(seed_b.as_slice())),
+                ],
+            },
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            player_info.clone(),
+            shoot.clone(),
+        )
+        .unwrap();
+
+        // the owner cannot judge a commit-reveal shot
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::GoalShot {
+                shot_id: 1,
+                attestation: None,
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::WrongResolutionMode { shot_id: 1, .. }
+        ));
+
+        let signature = HexBinary::from_hex(QUICKNET_ROUND_123).unwrap();
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::RevealShot {
+                shot_id: 1,
+                server_seed: seed_b.clone(),
+                signature: signature.clone(),
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidReveal { shot_id: 1 }));
+
+        // the seed alone does not settle the shot, the beacon of its round is mixed in
+        let mut tampered = signature.to_vec();
+        tampered[47] ^= 1;
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::RevealShot {
+                shot_id: 1,
+                server_seed: seed_a.clone(),
+                signature: HexBinary::from(tampered),
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::InvalidDrandBeacon { round: 123 }
+        ));
+
+        let roll = roll_bps(&commit_reveal_randomness(
+            seed_a.as_slice(),
+            "lucky",
+            1,
+            signature.as_slice(),
+        ));
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::RevealShot {
+                shot_id: 1,
+                server_seed: seed_a.clone(),
+                signature: signature.clone(),
+            },
+        )
+        .unwrap();
+        assert!(res.attributes.contains(&attr("round", "123")));
+        assert!(res.attributes.contains(&attr("roll", roll.to_string())));
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::QueryShot { shot_id: 1 },
+        )
+        .unwrap();
+        assert_eq!(
+            from_json::<ShotResponse>(&res).unwrap().status,
+            ShotStatus::Goal
+        );
+
+        // with no chance of a goal the second shot is a miss
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            update_config(ConfigUpdate {
+                goal_probability_bps: Some(0),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+        execute(deps.as_mut(), env.clone(), player_info, shoot).unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::RevealShot {
+                shot_id: 2,
+                server_seed: seed_b,
+                signature,
+            },
+        )
+        .unwrap();
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::QueryShot { shot_id: 2 },
+        )
+        .unwrap();
+        assert_eq!(
+            from_json::<ShotResponse>(&res).unwrap().status,
+            ShotStatus::Miss
+        );
+
+        let res = query(
+            deps.as_ref(),
+            env,
+            QueryMsg::QuerySeedCommitment { commitment_id: 1 },
+        )
+        .unwrap();
+        let commitment: SeedCommitmentResponse = from_json(&res).unwrap();
+        assert_eq!(commitment.shot_id, Some(1));
+        assert_eq!(commitment.server_seed, Some(seed_a));
+    }
+
+    // quicknet, https://api.drand.sh/52db9ba70e0cc0f6eaf7803dd07447a1f5477735fd3f661792ba94600c84e971/info
+    const QUICKNET_PUBKEY: &str = "83cf0f2896adee7eb8b5f01fcad3912212c437e0073e911fb90022d3e760183c8c4b450b6a0a6c3ac6a5776a2d1064510d1fec758c921cc22b0e17e63aaf4bcb5ed66304de9cf809bd274ca73bab4af5a6e9c76a4bc09e76eae8991ef5ece45a";
+    const QUICKNET_GENESIS: u64 = 1692803367;
+    // recorded beacon of round 123
+    const QUICKNET_ROUND_123: &str = "b75c69d0b72a5d906e854e808ba7e2accb1542ac355ae486d591aa9d43765482e26cd02df835d3546d23c4b13e0dfc92";
+
+    #[test]
+    fn test_drand_shot() {
+        let mut env = mock_env();
+        // round 122 is the latest published one
+        env.block.time = Timestamp::from_seconds(QUICKNET_GENESIS + 121 * 3 + 1);
+        let main_denom = "usei";
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("creator", &[]);
+        let msg = InstantiateMsg {
+            owner: None,
+            main_denom: main_denom.to_string(),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            update_config(ConfigUpdate {
+                resolution_mode: Some(ResolutionMode::Drand),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            update_config(ConfigUpdate {
+                goal_probability_bps: Some(10000),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+        let player_info = mock_info("player", &[Coin::new(0, main_denom)]);
+        let shoot = ExecuteMsg::Shoot {
+            client_seed: None,
+            kick: None,
+        };
+
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            player_info.clone(),
+            shoot.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::DrandNotConfigured {}));
+
+        // the public key must be a valid G2 point
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::SetDrandConfig {
+                new_drand_config: DrandConfig {
+                    pubkey: HexBinary::from(vec![1u8; 96]),
+                    genesis_time: QUICKNET_GENESIS,
+                    period: 3,
+                },
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidDrandConfig { .. }));
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            ExecuteMsg::SetDrandConfig {
+                new_drand_config: DrandConfig {
+                    pubkey: HexBinary::from_hex(QUICKNET_PUBKEY).unwrap(),
+                    genesis_time: QUICKNET_GENESIS,
+                    period: 3,
+                },
+            },
+        )
+        .unwrap();
+        execute(deps.as_mut(), env.clone(), player_info, shoot).unwrap();
+
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::QueryShot { shot_id: 1 },
+        )
+        .unwrap();
+        assert_eq!(
+            from_json::<ShotResponse>(&res).unwrap().resolution,
+            ShotResolution::Drand {
+                round: 123,
+                goal_probability_bps: 10000,
+            }
+        );
+
+        // a tampered signature is rejected
+        let signature = HexBinary::from_hex(QUICKNET_ROUND_123).unwrap();
+        let mut tampered = signature.to_vec();
+        tampered[47] ^= 1;
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::ResolveDrandShot {
+                shot_id: 1,
+                signature: HexBinary::from(tampered),
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::InvalidDrandBeacon { round: 123 }
+        ));
+
+        // the beacon is public by now, the player cannot refund a miss instead of resolving it
+        env.block.time = env
+            .block
+            .time
+            .plus_seconds(DEFAULT_SHOOT_DURATION + DEFAULT_REFUND_GRACE_PERIOD);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("player", &[]),
+            ExecuteMsg::RefundShot { shot_id: 1 },
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::RefundNotAllowed { shot_id: 1, .. }
+        ));
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::ExpireShots { limit: None },
+        )
+        .unwrap();
+        assert!(res.attributes.contains(&attr("expired_count", "0")));
+
+        // and anyone can still resolve it past the deadline
+        let roll = roll_bps(&drand_shot_randomness(signature.as_slice(), 1));
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::ResolveDrandShot {
+                shot_id: 1,
+                signature,
+            },
+        )
+        .unwrap();
+        assert!(res.attributes.contains(&attr("round", "123")));
+        assert!(res.attributes.contains(&attr("roll", roll.to_string())));
+        let res = query(deps.as_ref(), env, QueryMsg::QueryShot { shot_id: 1 }).unwrap();
+        assert_eq!(
+            from_json::<ShotResponse>(&res).unwrap().status,
+            ShotStatus::Goal
+        );
+    }
+
+    fn sign_attestation(
+        signing_key: &k256::ecdsa::SigningKey,
+        payload: &AttestationPayload,
+    ) -> HexBinary {
+        use k256::ecdsa::signature::hazmat::PrehashSigner;
+        let hash = sha256(&to_json_vec(payload).unwrap());
+        let signature: k256::ecdsa::Signature = signing_key.sign_prehash(&hash).unwrap();
+        HexBinary::from(signature.to_vec())
+    }
+
+    #[test]
+    fn test_attested_goal_shot() {
+        let env = mock_env();
+        let main_denom = "usei";
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("creator", &[]);
+        let msg = InstantiateMsg {
+            owner: None,
+            main_denom: main_denom.to_string(),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        let player_info = mock_info("player", &[Coin::new(0, main_denom)]);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            player_info,
+            ExecuteMsg::Shoot {
+                client_seed: None,
+                kick: None,
+            },
+        )
+        .unwrap();
+
+        let signing_key = k256::ecdsa::SigningKey::from_slice(&[7u8; 32]).unwrap();
+        let expires_at = env.block.time.seconds() + 60;
+        let mut payload = AttestationPayload {
+            contract: env.contract.address.to_string(),
+            shot_id: 1,
+            player: "player".to_string(),
+            outcome: ShotStatus::Goal,
+            expires_at,
+        };
+        let goal = |signature: HexBinary| ExecuteMsg::GoalShot {
+            shot_id: 1,
+            attestation: Some(ResultAttestation {
+                expires_at,
+                signature,
+            }),
+        };
+
+        // no key registered yet
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("relayer", &[]),
+            goal(sign_attestation(&signing_key, &payload)),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::AttestationKeyNotConfigured {}));
+
+        let pubkey = signing_key.verifying_key().to_encoded_point(true);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            ExecuteMsg::SetAttestationKey {
+                new_attestation_key: Some(AttestationKey::Secp256k1(HexBinary::from(
+                    pubkey.as_bytes(),
+                ))),
+            },
+        )
+        .unwrap();
+
+        // a miss attestation cannot be used to score a goal
+        payload.outcome = ShotStatus::Miss;
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("relayer", &[]),
+            goal(sign_attestation(&signing_key, &payload)),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::InvalidAttestation { shot_id: 1 }
+        ));
+
+        // nor an expired one
+        payload.outcome = ShotStatus::Goal;
+        let mut late_env = env.clone();
+        late_env.block.time = late_env.block.time.plus_seconds(60);
+        let err = execute(
+            deps.as_mut(),
+            late_env,
+            mock_info("relayer", &[]),
+            goal(sign_attestation(&signing_key, &payload)),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::AttestationExpired { shot_id: 1 }
+        ));
+
+        // any relayer can submit a valid attestation
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("relayer", &[]),
+            goal(sign_attestation(&signing_key, &payload)),
+        )
+        .unwrap();
+        let res = query(deps.as_ref(), env, QueryMsg::QueryShot { shot_id: 1 }).unwrap();
+        assert_eq!(
+            from_json::<ShotResponse>(&res).unwrap().status,
+            ShotStatus::Goal
+        );
+    }
+
+    #[test]
+    fn test_referee_quorum() {
+        let env = mock_env();
+        let main_denom = "usei";
+        let mut deps = mock_dependencies_with_balances(&[(
+            env.contract.address.as_str(),
+            &[Coin::new(100, main_denom)],
+        )]);
+
+        let info = mock_info("creator", &[]);
+        let msg = InstantiateMsg {
+            owner: None,
+            main_denom: main_denom.to_string(),
+            roles: Some(vec![RoleGrant {
+                address: "creator".to_string(),
+                role: Role::Treasury,
+            }]),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        for referee in ["referee_a", "referee_b", "referee_c"] {
+            let res = execute(
+                deps.as_mut(),
+                env.clone(),
+                info.clone(),
+                ExecuteMsg::GrantRole {
+                    address: referee.to_string(),
+                    role: Role::Referee,
+                },
+            )
+            .unwrap();
+            assert_eq!(res.events[0].ty, "role_granted");
+        }
+        for msg in [
+            ExecuteMsg::SetRefereeThreshold { new_threshold: 3 },
+            update_config(ConfigUpdate {
+                resolution_mode: Some(ResolutionMode::RefereeQuorum),
+                ..Default::default()
+            }),
+            ExecuteMsg::SyncBalance {
+                bucket: PoolBucket::Jackpot,
+            },
+        ] {
+            execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        }
+
+        // the quorum must stay reachable
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::RevokeRole {
+                address: "referee_c".to_string(),
+                role: Role::Referee,
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::InvalidRefereeThreshold {
+                threshold: 3,
+                referees: 2
             }
+        ));
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::SetRefereeThreshold { new_threshold: 2 },
+        )
+        .unwrap();
+
+        let player_info = mock_info("player", &[Coin::new(0, main_denom)]);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            player_info,
+            ExecuteMsg::Shoot {
+                client_seed: None,
+                kick: None,
+            },
+        )
+        .unwrap();
+
+        // the owner alone cannot pay out
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            ExecuteMsg::GoalShot {
+                shot_id: 1,
+                attestation: None,
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::WrongResolutionMode { shot_id: 1, .. }
+        ));
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("player", &[]),
+            ExecuteMsg::VoteGoal { shot_id: 1 },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::MissingRole { role } if role == "referee"));
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("referee_a", &[]),
+            ExecuteMsg::VoteGoal { shot_id: 1 },
+        )
+        .unwrap();
+        assert!(res.attributes.contains(&attr("votes", "1")));
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("referee_a", &[]),
+            ExecuteMsg::VoteGoal { shot_id: 1 },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::AlreadyVoted { shot_id: 1 }));
+
+        // the second vote reaches the threshold and pays the prize
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("referee_c", &[]),
+            ExecuteMsg::VoteGoal { shot_id: 1 },
+        )
+        .unwrap();
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::QueryClaimable {
+                address: Addr::unchecked("player"),
+            },
+        )
+        .unwrap();
+        assert_eq!(from_json::<ClaimableResponse>(&res).unwrap().amount, 80);
+
+        let res = query(deps.as_ref(), env, QueryMsg::QueryGoalVotes { shot_id: 1 }).unwrap();
+        let votes: GoalVotesResponse = from_json(&res).unwrap();
+        assert_eq!(
+            votes
+                .votes
+                .iter()
+                .map(|vote| vote.referee.as_str())
+                .collect::<Vec<_>>(),
+            vec!["referee_a", "referee_c"]
+        );
+    }
+
+    #[test]
+    fn test_dispute_window() {
+        let mut env = mock_env();
+        let main_denom = "usei";
+        let mut deps = mock_dependencies_with_balances(&[(
+            env.contract.address.as_str(),
+            &[Coin::new(100, main_denom)],
+        )]);
+
+        let info = mock_info("creator", &[]);
+        let msg = InstantiateMsg {
+            owner: None,
+            main_denom: main_denom.to_string(),
+            roles: operator_roles("creator"),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::SetDisputeConfig {
+                new_dispute_config: Some(DisputeConfig {
+                    threshold: 50,
+                    period: MAX_DISPUTE_PERIOD + 1,
+                    guardian: Addr::unchecked("guardian"),
+                }),
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidDisputePeriod { .. }));
+        for msg in [
+            ExecuteMsg::SetDisputeConfig {
+                new_dispute_config: Some(DisputeConfig {
+                    threshold: 50,
+                    period: 3600,
+                    guardian: Addr::unchecked("guardian"),
+                }),
+            },
+            ExecuteMsg::SyncBalance {
+                bucket: PoolBucket::Jackpot,
+            },
+        ] {
+            execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        }
+        let player_info = mock_info("player", &[Coin::new(0, main_denom)]);
+        let goal = |shot_id| ExecuteMsg::GoalShot {
+            shot_id,
+            attestation: None,
+        };
+
+        // the prize of 80 is above the threshold and held
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            player_info.clone(),
+            ExecuteMsg::Shoot {
+                client_seed: None,
+                kick: None,
+            },
+        )
+        .unwrap();
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), goal(1)).unwrap();
+        assert!(res.messages.is_empty());
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::QueryLedger {}).unwrap();
+        let ledger: LedgerResponse = from_json(&res).unwrap();
+        assert_eq!((ledger.jackpot, ledger.settling), (16, 84));
+
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::Finalize { shot_id: 1 },
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::DisputeWindowOpen { shot_id: 1, .. }
+        ));
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::Dispute { shot_id: 1 },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+
+        // the guardian freezes the payout and voids the goal
+        let guardian_info = mock_info("guardian", &[]);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            guardian_info.clone(),
+            ExecuteMsg::Dispute { shot_id: 1 },
+        )
+        .unwrap();
+        let mut late_env = env.clone();
+        late_env.block.time = late_env.block.time.plus_seconds(3600);
+        let err = execute(
+            deps.as_mut(),
+            late_env.clone(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::Finalize { shot_id: 1 },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::ShotDisputed { shot_id: 1 }));
+        // the guardian stays in place until the dispute is resolved
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::SetDisputeConfig {
+                new_dispute_config: None,
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::GuardianLocked {}));
+        execute(
+            deps.as_mut(),
+            late_env.clone(),
+            guardian_info,
+            ExecuteMsg::ResolveDispute {
+                shot_id: 1,
+                uphold: false,
+            },
+        )
+        .unwrap();
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::QueryShot { shot_id: 1 },
+        )
+        .unwrap();
+        assert_eq!(
+            from_json::<ShotResponse>(&res).unwrap().status,
+            ShotStatus::Voided
+        );
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::QueryBalance {}).unwrap();
+        assert_eq!(
+            from_json::<ContractBalanceResponse>(&res).unwrap().amount,
+            100
+        );
+
+        // an undisputed goal is released after the window
+        env.block.time = late_env.block.time;
+        for (funds, msg) in [
+            (
+                0,
+                ExecuteMsg::SetBondConfig {
+                    new_bond_config: BondConfig {
+                        unbonding_period: 0,
+                        slash_bps: DEFAULT_SLASH_BPS,
+                    },
+                },
+            ),
+            (1000, ExecuteMsg::Bond {}),
+            (0, ExecuteMsg::Unbond { amount: 1000 }),
+        ] {
+            let funds = if funds > 0 {
+                vec![Coin::new(funds, main_denom)]
+            } else {
+                vec![]
+            };
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                mock_info("creator", &funds),
+                msg,
+            )
+            .unwrap();
+        }
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            player_info,
+            ExecuteMsg::Shoot {
+                client_seed: None,
+                kick: None,
+            },
+        )
+        .unwrap();
+        execute(deps.as_mut(), env.clone(), info.clone(), goal(2)).unwrap();
+        // the bond stays put while the goal can still be disputed
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::WithdrawBond {},
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::DisputePending {}));
+        env.block.time = env.block.time.plus_seconds(3600);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            ExecuteMsg::WithdrawBond {},
+        )
+        .unwrap();
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::Finalize { shot_id: 2 },
+        )
+        .unwrap();
+        assert!(res.attributes.contains(&attr("reward_claimable", "80")));
+        let res = query(deps.as_ref(), env, QueryMsg::QueryShot { shot_id: 2 }).unwrap();
+        assert_eq!(
+            from_json::<ShotResponse>(&res).unwrap().status,
+            ShotStatus::Goal
+        );
+    }
+
+    #[test]
+    fn test_physics_shot() {
+        let mut env = mock_env();
+        // shots are bound to drand round 123
+        env.block.time = Timestamp::from_seconds(QUICKNET_GENESIS + 121 * 3 + 1);
+        let main_denom = "usei";
+        let mut deps = mock_dependencies_with_balances(&[(
+            env.contract.address.as_str(),
+            &[Coin::new(100, main_denom)],
+        )]);
+
+        let info = mock_info("creator", &[]);
+        let msg = InstantiateMsg {
+            owner: None,
+            main_denom: main_denom.to_string(),
+            roles: operator_roles("creator"),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        // the operator commits the keeper position in the first two bytes of each seed
+        let keeper_seed = |position: i32, salt: u8| {
+            let mut seed = ((position + physics::POST) as u16).to_be_bytes().to_vec();
+            seed.extend([salt; 30]);
+            HexBinary::from(seed)
+        };
+        let seed_a = keeper_seed(-600, 3);
+        let seed_b = keeper_seed(-600, 4);
+        let seed_c = keeper_seed(-600, 5);
+        for msg in [
+            update_config(ConfigUpdate {
+                resolution_mode: Some(ResolutionMode::Physics),
+                ..Default::default()
+            }),
+            update_config(ConfigUpdate {
+                goal_probability_bps: Some(10000),
+                ..Default::default()
+            }),
+            ExecuteMsg::SetDrandConfig {
+                new_drand_config: DrandConfig {
+                    pubkey: HexBinary::from_hex(QUICKNET_PUBKEY).unwrap(),
+                    genesis_time: QUICKNET_GENESIS,
+                    period: 3,
+                },
+            },
+            ExecuteMsg::CommitSeeds {
+                commitments: vec![
+                    HexBinary::from(sha256(seed_a.as_slice())),
+                    HexBinary::from(sha256(seed_b.as_slice())),
+                    HexBinary::from(sha256(seed_c.as_slice())),
+                ],
+            },
+            ExecuteMsg::SyncBalance {
+                bucket: PoolBucket::Jackpot,
+            },
+        ] {
+            execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        }
+        // the bond backs the prize of every shot until its seed is revealed
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("creator", &[Coin::new(240, main_denom)]),
+            ExecuteMsg::Bond {},
+        )
+        .unwrap();
+        let player_info = mock_info("player", &[Coin::new(0, main_denom)]);
+
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            player_info.clone(),
+            ExecuteMsg::Shoot {
+                client_seed: None,
+                kick: None,
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidKick {}));
+
+        // the first kick goes away from the committed keeper, the second one straight at it
+        let away = Kick {
+            direction: 600,
+            power: 600,
+            curve: 0,
+        };
+        let at_keeper = Kick {
+            direction: -600,
+            power: 600,
+            curve: 0,
+        };
+        let signature = HexBinary::from_hex(QUICKNET_ROUND_123).unwrap();
+        for kick in [away.clone(), at_keeper] {
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                player_info.clone(),
+                ExecuteMsg::Shoot {
+                    client_seed: None,
+                    kick: Some(kick),
+                },
+            )
+            .unwrap();
+        }
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::RevealShot {
+                shot_id: 1,
+                server_seed: seed_a,
+                signature: signature.clone(),
+            },
+        )
+        .unwrap();
+        assert!(res.attributes.contains(&attr("keeper_position", "-600")));
+        assert!(res.attributes.contains(&attr("kick_result", "goal")));
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::RevealShot {
+                shot_id: 2,
+                server_seed: seed_b,
+                signature: signature.clone(),
+            },
+        )
+        .unwrap();
+        assert!(res.attributes.contains(&attr("kick_result", "saved")));
+
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::QueryShot { shot_id: 1 },
+        )
+        .unwrap();
+        assert_eq!(
+            from_json::<ShotResponse>(&res).unwrap().status,
+            ShotStatus::Goal
+        );
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::QueryShot { shot_id: 2 },
+        )
+        .unwrap();
+        assert_eq!(
+            from_json::<ShotResponse>(&res).unwrap().status,
+            ShotStatus::Miss
+        );
+
+        // beating the keeper is not enough without a chance to finish
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            update_config(ConfigUpdate {
+                goal_probability_bps: Some(0),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            player_info,
+            ExecuteMsg::Shoot {
+                client_seed: None,
+                kick: Some(away),
+            },
+        )
+        .unwrap();
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::RevealShot {
+                shot_id: 3,
+                server_seed: seed_c,
+                signature,
+            },
+        )
+        .unwrap();
+        assert!(res.attributes.contains(&attr("kick_result", "goal")));
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::QueryShot { shot_id: 3 },
+        )
+        .unwrap();
+        assert_eq!(
+            from_json::<ShotResponse>(&res).unwrap().status,
+            ShotStatus::Miss
         );
+        // every seed was revealed, nothing is left locked in the bond
+        let res = query(deps.as_ref(), env, QueryMsg::QueryBond {}).unwrap();
+        assert_eq!(from_json::<BondResponse>(&res).unwrap().locked, 0);
     }
 
     #[test]
-    fn test_set_ticket_price() {
+    fn test_physics_goal_rate() {
+        // wherever the operator puts the keeper, no kick scores more often than configured
+        let signature = HexBinary::from_hex(QUICKNET_ROUND_123).unwrap();
+        let goal_probability_bps = 2500;
+        let kicks = [
+            Kick {
+                direction: 999,
+                power: 900,
+                curve: 0,
+            },
+            Kick {
+                direction: 0,
+                power: 600,
+                curve: 0,
+            },
+            Kick {
+                direction: 700,
+                power: 900,
+                curve: 900,
+            },
+        ];
+        for kick in kicks.iter() {
+            let goals = (1..=2000u64)
+                .filter(|shot_id| {
+                    let seed = sha256(&shot_id.to_be_bytes());
+                    let keeper = keeper_position(&seed).unwrap();
+                    let randomness = physics_shot_randomness(&seed, *shot_id, signature.as_slice());
+                    physics::simulate(kick, keeper) == KickResult::Goal
+                        && roll_bps(&randomness) < goal_probability_bps
+                })
+                .count();
+            assert!(goals * 10000 / 2000 <= goal_probability_bps as usize);
+        }
+
+        // a kick the keeper reaches never scores
+        assert_eq!(physics::simulate(&kicks[0], 850), KickResult::Saved);
+        assert_eq!(keeper_position(&[0x07, 0xd0]), Some(physics::POST));
+        assert_eq!(keeper_position(&[0x07]), None);
+    }
+
+    #[test]
+    fn test_operator_bond_slash_and_unbond() {
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(QUICKNET_GENESIS);
+        let main_denom = "usei";
         let mut deps = mock_dependencies();
-        let env = mock_env();
+
         let info = mock_info("creator", &[]);
         let msg = InstantiateMsg {
             owner: None,
-            main_denom: "usei".to_string(),
+            main_denom: main_denom.to_string(),
+            roles: operator_roles("creator"),
+            ..Default::default()
         };
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
-        let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
-        assert_eq!(0, res.messages.len());
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::SetBondConfig {
+                new_bond_config: BondConfig {
+                    unbonding_period: MAX_UNBONDING_PERIOD + 1,
+                    slash_bps: DEFAULT_SLASH_BPS,
+                },
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidUnbondingPeriod { .. }));
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("creator", &[Coin::new(1000, "uatom")]),
+            ExecuteMsg::Bond {},
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidFund {}));
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("creator", &[Coin::new(1000, main_denom)]),
+            ExecuteMsg::Bond {},
+        )
+        .unwrap();
 
-        let msg = ExecuteMsg::SetTicketPrice {
-            new_ticket_price: 100,
+        // the operator never reveals the seed of a commit-reveal shot
+        let seed = HexBinary::from(vec![5u8; 32]);
+        for msg in [
+            update_config(ConfigUpdate {
+                ticket_price: Some(1000),
+                ..Default::default()
+            }),
+            update_config(ConfigUpdate {
+                resolution_mode: Some(ResolutionMode::CommitReveal),
+                ..Default::default()
+            }),
+            ExecuteMsg::SetDrandConfig {
+                new_drand_config: DrandConfig {
+                    pubkey: HexBinary::from_hex(QUICKNET_PUBKEY).unwrap(),
+                    genesis_time: QUICKNET_GENESIS,
+                    period: 3,
+                },
+            },
+            ExecuteMsg::CommitSeeds {
+                commitments: vec![HexBinary::from(sha256(seed.as_slice()))],
+            },
+        ] {
+            execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        }
+        let player_info = mock_info("player", &[Coin::new(1000, main_denom)]);
+        let shoot = ExecuteMsg::Shoot {
+            client_seed: Some("lucky".to_string()),
+            kick: None,
         };
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            player_info.clone(),
+            shoot.clone(),
+        )
+        .unwrap();
 
-        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        // the prize of 800 is locked in the bond, the next one is not covered
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::QueryBond {}).unwrap();
+        let bond: BondResponse = from_json(&res).unwrap();
+        assert_eq!((bond.bonded, bond.locked), (1000, 800));
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("other", &[Coin::new(1000, main_denom)]),
+            shoot,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::InsufficientBond {
+                required: 1600,
+                available: 200
+            }
+        ));
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::Unbond { amount: 300 },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InsufficientBalance {}));
+
+        // the player is paid the prize out of the bond instead of a refund
+        env.block.time = env
+            .block
+            .time
+            .plus_seconds(DEFAULT_SHOOT_DURATION + DEFAULT_REFUND_GRACE_PERIOD);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            player_info,
+            ExecuteMsg::RefundShot { shot_id: 1 },
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::RefundNotAllowed { shot_id: 1, .. }
+        ));
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::ExpireShots { limit: None },
+        )
+        .unwrap();
+        assert!(res.events.iter().any(|e| e.ty == "operator_slashed"));
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::QueryClaimable {
+                address: Addr::unchecked("player"),
+            },
+        )
+        .unwrap();
+        assert_eq!(from_json::<ClaimableResponse>(&res).unwrap().amount, 800);
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::QueryLedger {}).unwrap();
+        let ledger: LedgerResponse = from_json(&res).unwrap();
         assert_eq!(
-            res.attributes,
-            vec![
-                attr("method", "execute_set_ticket_price"),
-                attr("new_ticket_price", "100")
-            ]
+            (ledger.jackpot, ledger.claimable, ledger.bond),
+            (1000, 800, 200)
         );
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::QuerySlashes {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+        let slashes: SlashesResponse = from_json(&res).unwrap();
+        assert_eq!(slashes.slashes.len(), 1);
+        assert_eq!(slashes.slashes[0].shot_id, 1);
+        assert_eq!(slashes.slashes[0].amount, 800);
+        assert_eq!(slashes.slashes[0].reason, "unrevealed_seed");
 
-        let res = query(deps.as_ref(), env.clone(), QueryMsg::QueryConfig {}).unwrap();
-        let config: Config = from_binary(&res).unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::Unbond { amount: 100 },
+        )
+        .unwrap();
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::WithdrawBond {},
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::BondUnbonding { .. }));
+
+        env.block.time = env.block.time.plus_seconds(DEFAULT_UNBONDING_PERIOD);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            ExecuteMsg::WithdrawBond {},
+        )
+        .unwrap();
+        assert!(res.attributes.contains(&attr("amount", "100")));
+        let res = query(deps.as_ref(), env, QueryMsg::QueryBond {}).unwrap();
+        let bond: BondResponse = from_json(&res).unwrap();
         assert_eq!(
-            Config {
-                owner: Addr::unchecked("creator".to_string()),
-                ticket_price: 100,
-                reward_percentage: 80,
-                admin_percentage: 4,
-                shoot_duration: 90
-            },
-            config
+            (bond.bonded, bond.locked, bond.unbonding, bond.total_slashed),
+            (100, 0, 0, 800)
         );
     }
 
     #[test]
-    fn test_set_percentage() {
+    fn test_refund_unresolved_shot() {
+        let mut env = mock_env();
+        let main_denom = "usei";
         let mut deps = mock_dependencies();
-        let env = mock_env();
+
         let info = mock_info("creator", &[]);
         let msg = InstantiateMsg {
             owner: None,
-            main_denom: "usei".to_string(),
+            main_denom: main_denom.to_string(),
+            ..Default::default()
         };
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        for msg in [
+            update_config(ConfigUpdate {
+                ticket_price: Some(100),
+                ..Default::default()
+            }),
+            update_config(ConfigUpdate {
+                fee_schedule: Some(FeeSchedule {
+                    reward_bps: 8000,
+                    admin_bps: 0,
+                    reserve_bps: 0,
+                    ticket_fee_bps: 1000,
+                }),
+                ..Default::default()
+            }),
+            update_config(ConfigUpdate {
+                refund_grace_period: Some(600),
+                ..Default::default()
+            }),
+        ] {
+            execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        }
+        for player in ["player_a", "player_b"] {
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                mock_info(player, &[Coin::new(100, main_denom)]),
+                ExecuteMsg::Shoot {
+                    client_seed: None,
+                    kick: None,
+                },
+            )
+            .unwrap();
+        }
 
-        let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
-        assert_eq!(0, res.messages.len());
+        // nobody judged the shot, but the grace period is still running
+        env.block.time = env.block.time.plus_seconds(DEFAULT_SHOOT_DURATION);
+        let refund = ExecuteMsg::RefundShot { shot_id: 1 };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("player_a", &[]),
+            refund.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::RefundNotAvailable { shot_id: 1, .. }
+        ));
 
-        let msg = ExecuteMsg::SetRewardPercentage {
-            new_reward_percentage: 90,
-        };
+        env.block.time = env.block.time.plus_seconds(600);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("player_b", &[]),
+            refund.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("player_a", &[]),
+            refund.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "player_a".to_string(),
+                amount: vec![Coin::new(100, main_denom)],
+            })
+        );
 
-        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        // the ticket is taken off both the jackpot and the fee bucket
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::QueryLedger {}).unwrap();
+        let ledger: LedgerResponse = from_json(&res).unwrap();
+        assert_eq!((ledger.jackpot, ledger.admin_fees), (90, 10));
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::QueryShot { shot_id: 1 },
+        )
+        .unwrap();
         assert_eq!(
-            res.attributes,
-            vec![
-                attr("method", "execute_set_reward_percentage"),
-                attr("new_reward_percentage", "90")
-            ]
+            from_json::<ShotResponse>(&res).unwrap().status,
+            ShotStatus::Refunded
         );
 
-        let res = query(deps.as_ref(), env.clone(), QueryMsg::QueryConfig {}).unwrap();
-        let config: Config = from_binary(&res).unwrap();
+        let err = execute(deps.as_mut(), env, mock_info("player_a", &[]), refund).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::ShotAlreadyResolved { shot_id: 1, .. }
+        ));
+    }
+
+    #[test]
+    fn test_liveness_degraded_mode() {
+        let mut env = mock_env();
+        let main_denom = "usei";
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("creator", &[]);
+        let msg = InstantiateMsg {
+            owner: None,
+            main_denom: main_denom.to_string(),
+            roles: operator_roles("creator"),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        // the referee gets at least the longest shoot duration
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            update_config(ConfigUpdate {
+                liveness_period: Some(0),
+                ..Default::default()
+            }),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidLivenessPeriod { .. }));
+        for msg in [
+            update_config(ConfigUpdate {
+                ticket_price: Some(100),
+                ..Default::default()
+            }),
+            update_config(ConfigUpdate {
+                liveness_period: Some(MAX_SHOOT_DURATION),
+                ..Default::default()
+            }),
+        ] {
+            execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        }
+        let player_info = mock_info("player", &[Coin::new(100, main_denom)]);
+        let shoot = ExecuteMsg::Shoot {
+            client_seed: None,
+            kick: None,
+        };
+        for _ in 0..2 {
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                player_info.clone(),
+                shoot.clone(),
+            )
+            .unwrap();
+        }
+
+        // the referee stays silent for the whole liveness period
+        env.block.time = env.block.time.plus_seconds(MAX_SHOOT_DURATION);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            player_info.clone(),
+            shoot.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::DegradedMode {}));
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::CheckLiveness {},
+        )
+        .unwrap();
+        assert_eq!(res.events[0].ty, "degraded_mode");
+
+        // pending tickets are refundable without waiting for the grace period
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("player", &[]),
+            ExecuteMsg::RefundShot { shot_id: 1 },
+        )
+        .unwrap();
+
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::ResumeNormalMode {},
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::BacklogNotResolved { pending: 1 }
+        ));
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::MissShot {
+                shot_id: 2,
+                attestation: None,
+            },
+        )
+        .unwrap();
+
+        // a late resolution does not leave degraded mode on its own
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::QueryLiveness {}).unwrap();
+        let liveness: LivenessResponse = from_json(&res).unwrap();
+        assert!(liveness.degraded);
+        assert_eq!(liveness.awaiting_since, None);
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            ExecuteMsg::ResumeNormalMode {},
+        )
+        .unwrap();
+        execute(deps.as_mut(), env, player_info, shoot).unwrap();
+    }
+
+    #[test]
+    fn test_liveness_released_by_expire_and_refund() {
+        let mut env = mock_env();
+        let start = env.block.time.seconds();
+        let main_denom = "usei";
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("creator", &[]);
+        let msg = InstantiateMsg {
+            owner: None,
+            main_denom: main_denom.to_string(),
+            refund_grace_period: Some(600),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+        let player_info = mock_info("player", &[Coin::new(0, main_denom)]);
+        let shoot = ExecuteMsg::Shoot {
+            client_seed: None,
+            kick: None,
+        };
+        let awaiting_since = |deps: Deps, env: &Env| {
+            let res = query(deps, env.clone(), QueryMsg::QueryLiveness {}).unwrap();
+            from_json::<LivenessResponse>(&res).unwrap().awaiting_since
+        };
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            player_info.clone(),
+            shoot.clone(),
+        )
+        .unwrap();
+        env.block.time = env.block.time.plus_seconds(50);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            player_info.clone(),
+            shoot.clone(),
+        )
+        .unwrap();
+
+        // the clock restarts from the shot that is still waiting
+        env.block.time = Timestamp::from_seconds(start + DEFAULT_SHOOT_DURATION);
+        let expire = ExecuteMsg::ExpireShots { limit: None };
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            expire.clone(),
+        )
+        .unwrap();
+        assert!(res.attributes.contains(&attr("expired_count", "1")));
+        assert_eq!(awaiting_since(deps.as_ref(), &env), Some(start + 50));
+
+        env.block.time = env.block.time.plus_seconds(50);
+        execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), expire).unwrap();
+        assert_eq!(awaiting_since(deps.as_ref(), &env), None);
+
+        // a refunded shot stops the clock as well
+        execute(deps.as_mut(), env.clone(), player_info.clone(), shoot).unwrap();
         assert_eq!(
-            Config {
-                owner: Addr::unchecked("creator".to_string()),
-                ticket_price: 0,
-                reward_percentage: 90,
-                admin_percentage: 4,
-                shoot_duration: 90
+            awaiting_since(deps.as_ref(), &env),
+            Some(env.block.time.seconds())
+        );
+        env.block.time = env.block.time.plus_seconds(DEFAULT_SHOOT_DURATION + 600);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            player_info,
+            ExecuteMsg::RefundShot { shot_id: 3 },
+        )
+        .unwrap();
+        assert_eq!(awaiting_since(deps.as_ref(), &env), None);
+    }
+
+    #[test]
+    fn test_ownership_transfer_and_renounce() {
+        let env = mock_env();
+        let mut deps = mock_dependencies();
+
+        let info = mock_info("creator", &[]);
+        let msg = InstantiateMsg {
+            owner: None,
+            main_denom: "usei".to_string(),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        let expiry = env.block.time.seconds() + 100;
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::ProposeNewOwner {
+                address: "new_owner".to_string(),
+                expiry: Some(expiry),
             },
-            config
+        )
+        .unwrap();
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::QueryPendingOwner {}).unwrap();
+        assert_eq!(
+            from_json::<PendingOwnerResponse>(&res).unwrap(),
+            PendingOwnerResponse {
+                pending_owner: Some("new_owner".to_string()),
+                expires_at: Some(expiry),
+            }
         );
 
-        let msg = ExecuteMsg::SetAdminPercentage {
-            new_admin_percentage: 10,
+        // only the proposed address can take over
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("new_ownr", &[]),
+            ExecuteMsg::AcceptOwnership {},
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        let mut late_env = env.clone();
+        late_env.block.time = late_env.block.time.plus_seconds(100);
+        let err = execute(
+            deps.as_mut(),
+            late_env,
+            mock_info("new_owner", &[]),
+            ExecuteMsg::AcceptOwnership {},
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::OwnershipProposalExpired { .. }
+        ));
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("new_owner", &[]),
+            ExecuteMsg::AcceptOwnership {},
+        )
+        .unwrap();
+        assert_eq!(res.events[0].ty, "ownership_transferred");
+        let set_price = update_config(ConfigUpdate {
+            ticket_price: Some(10),
+            ..Default::default()
+        });
+        let err = execute(deps.as_mut(), env.clone(), info, set_price.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::MissingRole { role } if role == "admin"));
+
+        // after renouncing nobody owns the contract
+        let new_owner_info = mock_info("new_owner", &[]);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            new_owner_info.clone(),
+            ExecuteMsg::RenounceOwnership {},
+        )
+        .unwrap();
+        let err = execute(deps.as_mut(), env.clone(), new_owner_info, set_price).unwrap_err();
+        assert!(matches!(err, ContractError::MissingRole { role } if role == "admin"));
+        let res = query(deps.as_ref(), env, QueryMsg::QueryConfig {}).unwrap();
+        assert_eq!(from_json::<ConfigResponse>(&res).unwrap().owner, None);
+    }
+
+    #[test]
+    fn test_roles() {
+        let env = mock_env();
+        let mut deps = mock_dependencies();
+        let info = mock_info("creator", &[]);
+        let msg = InstantiateMsg {
+            owner: None,
+            main_denom: "usei".to_string(),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        for (address, role) in [
+            ("admin", Role::Admin),
+            ("treasury", Role::Treasury),
+            ("referee", Role::Referee),
+        ] {
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                info.clone(),
+                ExecuteMsg::GrantRole {
+                    address: address.to_string(),
+                    role,
+                },
+            )
+            .unwrap();
+        }
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::QueryRoles {}).unwrap();
+        let roles = from_json::<RolesResponse>(&res).unwrap();
+        assert_eq!(roles.owner, Some("creator".to_string()));
+        assert_eq!(
+            roles.roles[1],
+            RoleHolders {
+                role: Role::Referee,
+                addresses: vec!["referee".to_string()],
+            }
+        );
+        assert!(roles.roles[3].addresses.is_empty());
+
+        // each role only opens its own paths
+        let set_price = update_config(ConfigUpdate {
+            ticket_price: Some(10),
+            ..Default::default()
+        });
+        let withdraw = ExecuteMsg::WithdrawFees {
+            amount: 1,
+            recipient: None,
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("referee", &[]),
+            set_price.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::MissingRole { role } if role == "admin"));
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("admin", &[]),
+            withdraw.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::MissingRole { role } if role == "treasury"));
+        // the owner is implicitly an admin, but not a treasurer
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), withdraw.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::MissingRole { role } if role == "treasury"));
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("treasury", &[]),
+            withdraw,
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InsufficientBalance {}));
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("admin", &[]),
+            set_price.clone(),
+        )
+        .unwrap();
+
+        // an admin can revoke roles, but only granted ones
+        let revoke = ExecuteMsg::RevokeRole {
+            address: "admin".to_string(),
+            role: Role::Admin,
+        };
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("admin", &[]),
+            revoke.clone(),
+        )
+        .unwrap();
+        assert_eq!(res.events[0].ty, "role_revoked");
+        let err = execute(deps.as_mut(), env.clone(), info, revoke).unwrap_err();
+        assert!(matches!(err, ContractError::RoleNotGranted { .. }));
+        let err = execute(deps.as_mut(), env, mock_info("admin", &[]), set_price).unwrap_err();
+        assert!(matches!(err, ContractError::MissingRole { role } if role == "admin"));
+    }
+
+    #[test]
+    fn test_pause_switches() {
+        let mut env = mock_env();
+        let mut deps = mock_dependencies();
+        let info = mock_info("creator", &[]);
+        let msg = InstantiateMsg {
+            owner: None,
+            main_denom: "usei".to_string(),
+            roles: operator_roles("creator"),
+            ..Default::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::GrantRole {
+                address: "pauser".to_string(),
+                role: Role::Pauser,
+            },
+        )
+        .unwrap();
+        let pauser_info = mock_info("pauser", &[]);
+        for what in [PauseTarget::Shooting, PauseTarget::FeeWithdrawals] {
+            let res = execute(
+                deps.as_mut(),
+                env.clone(),
+                pauser_info.clone(),
+                ExecuteMsg::Pause { what },
+            )
+            .unwrap();
+            assert_eq!(res.events[0].ty, "paused");
+        }
+
+        let shoot = ExecuteMsg::Shoot {
+            client_seed: None,
+            kick: None,
+        };
+        let player_info = mock_info("player", &[Coin::new(0, "usei")]);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            player_info.clone(),
+            shoot.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Paused { what } if what == "shooting"));
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::WithdrawFees {
+                amount: 1,
+                recipient: None,
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Paused { what } if what == "fee_withdrawals"));
+
+        // only an admin releases a switch, here with a scheduled release
+        let unpause = ExecuteMsg::Unpause {
+            what: PauseTarget::Shooting,
+            unpause_at: Some(env.block.time.seconds() + 100),
         };
-
-        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            pauser_info.clone(),
+            unpause.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::MissingRole { role } if role == "admin"));
+        execute(deps.as_mut(), env.clone(), info.clone(), unpause).unwrap();
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::QueryPauses {}).unwrap();
         assert_eq!(
-            res.attributes,
-            vec![
-                attr("method", "execute_set_admin_percentage"),
-                attr("new_admin_percentage", "10")
-            ]
+            from_json::<PausesResponse>(&res).unwrap().pauses[0],
+            PauseResponse {
+                what: PauseTarget::Shooting,
+                paused: true,
+                paused_at: Some(env.block.time.seconds()),
+                unpause_at: Some(env.block.time.seconds() + 100),
+            }
         );
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            player_info.clone(),
+            shoot.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Paused { .. }));
 
-        let res = query(deps.as_ref(), env.clone(), QueryMsg::QueryConfig {}).unwrap();
-        let config: Config = from_binary(&res).unwrap();
-        assert_eq!(
-            Config {
-                owner: Addr::unchecked("creator".to_string()),
-                ticket_price: 0,
-                reward_percentage: 90,
-                admin_percentage: 10,
-                shoot_duration: 90
+        env.block.time = env.block.time.plus_seconds(100);
+        execute(deps.as_mut(), env.clone(), player_info, shoot).unwrap();
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::Unpause {
+                what: PauseTarget::Shooting,
+                unpause_at: None,
             },
-            config
-        );
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::NotPaused { .. }));
+
+        // a paused resolution leaves the shot pending
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            pauser_info,
+            ExecuteMsg::Pause {
+                what: PauseTarget::Resolutions,
+            },
+        )
+        .unwrap();
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            ExecuteMsg::GoalShot {
+                shot_id: 1,
+                attestation: None,
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Paused { what } if what == "resolutions"));
+        // nor can it expire in the meantime
+        env.block.time = env.block.time.plus_seconds(DEFAULT_SHOOT_DURATION);
+        let err = execute(
+            deps.as_mut(),
+            env,
+            mock_info("anyone", &[]),
+            ExecuteMsg::ExpireShots { limit: None },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Paused { what } if what == "resolutions"));
     }
 
     #[test]
-    fn test_shoot_for_free_twice_success() {
-        let env = mock_env();
-        let main_denom = "usei";
-        let mut deps = mock_dependencies_with_balances(&[(
-            env.contract.address.as_str(),
-            &[Coin::new(0, main_denom)],
-        )]);
+    fn test_timelocked_config_change() {
+        let mut env = mock_env();
+        let mut deps = mock_dependencies();
         let info = mock_info("creator", &[]);
         let msg = InstantiateMsg {
             owner: None,
-            main_denom: main_denom.to_string(),
+            main_denom: "usei".to_string(),
+            roles: operator_roles("creator"),
+            ..Default::default()
         };
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            update_config(ConfigUpdate {
+                config_timelock: Some(MAX_CONFIG_TIMELOCK + 1),
+                ..Default::default()
+            }),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidConfigTimelock { .. }));
+        // without a timelock the change applies right away
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            update_config(ConfigUpdate {
+                config_timelock: Some(100),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
 
-        let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
-        assert_eq!(0, res.messages.len());
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            update_config(ConfigUpdate {
+                ticket_price: Some(50),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+        assert_eq!(res.events[0].ty, "config_change_queued");
+        assert_eq!(CONFIG.load(&deps.storage).unwrap().ticket_price, 0);
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::QueryConfigChanges {}).unwrap();
+        assert_eq!(
+            from_json::<ConfigChangesResponse>(&res).unwrap(),
+            ConfigChangesResponse {
+                config_timelock: 100,
+                changes: vec![ConfigChangeResponse {
+                    change_id: 1,
+                    change: ConfigChange::Update(ConfigUpdate {
+                        ticket_price: Some(50),
+                        ..Default::default()
+                    }),
+                    queued_at: env.block.time.seconds(),
+                    executable_at: env.block.time.seconds() + 100,
+                    expires_at: env.block.time.seconds() + 100 + CONFIG_CHANGE_GRACE_PERIOD,
+                }],
+            }
+        );
 
-        let ticket_price = CONFIG.load(deps.as_ref().storage).unwrap().ticket_price;
-        let msg = ExecuteMsg::Shoot {};
-        let info_with_funds = mock_info(
-            "creator",
-            &[Coin {
-                denom: main_denom.to_string(),
-                amount: Uint128::from(ticket_price),
-            }],
+        // so are the other player-facing settings, only the emergency switches are exempt
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            update_config(ConfigUpdate {
+                resolution_mode: Some(ResolutionMode::CommitReveal),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+        assert_eq!(res.events[0].ty, "config_change_queued");
+        assert_eq!(
+            CONFIG.load(&deps.storage).unwrap().resolution_mode,
+            ResolutionMode::Referee
         );
-        let res = execute(deps.as_mut(), env.clone(), info_with_funds.clone(), msg).unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::Pause {
+                what: PauseTarget::Shooting,
+            },
+        )
+        .unwrap();
+        assert!(PAUSES.has(&deps.storage, PauseTarget::Shooting.as_str()));
 
+        let apply = ExecuteMsg::ApplyConfigChange { change_id: 1 };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            apply.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::ConfigChangeNotReady { change_id: 1, .. }
+        ));
+        env.block.time = env.block.time.plus_seconds(100);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            apply.clone(),
+        )
+        .unwrap();
+        assert_eq!(CONFIG.load(&deps.storage).unwrap().ticket_price, 50);
+        let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), apply).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::ConfigChangeNotFound { change_id: 1 }
+        ));
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::ApplyConfigChange { change_id: 2 },
+        )
+        .unwrap();
         assert_eq!(
-            res.attributes,
-            vec![
-                attr("method", "execute_shoot"),
-                attr(
-                    "shoot_deadline",
-                    env.block.time.seconds().add(SHOOT_DURATION).to_string()
-                ),
-            ]
+            CONFIG.load(&deps.storage).unwrap().resolution_mode,
+            ResolutionMode::CommitReveal
         );
 
-        let res = query(
-            deps.as_ref(),
+        // an admin can cancel a queued change
+        execute(
+            deps.as_mut(),
             env.clone(),
-            QueryMsg::QueryShootDeadline {
-                address: Addr::unchecked("creator".to_string()),
-            },
+            info.clone(),
+            update_config(ConfigUpdate {
+                config_timelock: Some(0),
+                ..Default::default()
+            }),
         )
         .unwrap();
-        let shoot_deadline: u64 = from_binary::<ShootDeadlineResponse>(&res)
+        let cancel = ExecuteMsg::CancelConfigChange { change_id: 3 };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            cancel.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::MissingRole { role } if role == "admin"));
+        execute(deps.as_mut(), env.clone(), info.clone(), cancel).unwrap();
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::QueryConfigChanges {}).unwrap();
+        assert!(from_json::<ConfigChangesResponse>(&res)
             .unwrap()
-            .shoot_deadline;
-        assert_eq!(shoot_deadline, env.block.time.seconds().add(SHOOT_DURATION));
+            .changes
+            .is_empty());
+
+        // a change nobody applied within the grace period lapses
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            update_config(ConfigUpdate {
+                ticket_price: Some(60),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+        env.block.time = env
+            .block
+            .time
+            .plus_seconds(100 + CONFIG_CHANGE_GRACE_PERIOD);
+        let err = execute(
+            deps.as_mut(),
+            env,
+            mock_info("anyone", &[]),
+            ExecuteMsg::ApplyConfigChange { change_id: 4 },
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::ConfigChangeExpired { change_id: 4, .. }
+        ));
+        assert_eq!(CONFIG.load(&deps.storage).unwrap().ticket_price, 50);
     }
 
     #[test]
-    fn test_shoot_10ntrn_success() {
+    fn test_full_instantiate_and_update_config() {
         let env = mock_env();
-        let main_denom = "usei";
-        let mut deps = mock_dependencies_with_balances(&[
-            (
-                env.contract.address.as_str(),
-                &[Coin::new(1000, main_denom)],
-            ),
-            ("player", &[Coin::new(100, main_denom)]),
-        ]);
-
+        let mut deps = mock_dependencies();
         let info = mock_info("creator", &[]);
-        let msg = InstantiateMsg {
-            owner: Some("creator".to_string()),
-            main_denom: main_denom.to_string(),
+        let fee_schedule = FeeSchedule {
+            reward_bps: 7000,
+            admin_bps: 500,
+            reserve_bps: 500,
+            ticket_fee_bps: 100,
         };
-
-        let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
-        assert_eq!(0, res.messages.len());
-
-        // set ticket price
-        let msg = ExecuteMsg::SetTicketPrice {
-            new_ticket_price: 10,
+        let msg = InstantiateMsg {
+            owner: None,
+            main_denom: "usei".to_string(),
+            ticket_price: Some(100),
+            fee_schedule: Some(fee_schedule.clone()),
+            fee_recipients: None,
+            shoot_duration: Some(60),
+            cooldown_policy: Some(CooldownPolicy::UntilResolved),
+            resolution_mode: Some(ResolutionMode::RefereeQuorum),
+            goal_probability_bps: Some(500),
+            refund_grace_period: Some(600),
+            liveness_period: Some(3600),
+            config_timelock: Some(0),
+            roles: Some(vec![RoleGrant {
+                address: "referee".to_string(),
+                role: Role::Referee,
+            }]),
         };
-        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        // a bad setting fails the whole instantiation
+        let err = instantiate(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            InstantiateMsg {
+                shoot_duration: Some(MAX_SHOOT_DURATION + 1),
+                ..msg.clone()
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidShootDuration { .. }));
+        let err = instantiate(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            InstantiateMsg {
+                liveness_period: Some(0),
+                ..msg.clone()
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidLivenessPeriod { .. }));
+        let err = instantiate(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            InstantiateMsg {
+                config_timelock: Some(MAX_CONFIG_TIMELOCK + 1),
+                ..msg.clone()
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidConfigTimelock { .. }));
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
         assert_eq!(
-            res.attributes,
-            vec![
-                attr("method", "execute_set_ticket_price"),
-                attr("new_ticket_price", "10")
-            ]
-        );
-
-        let ticket_price = CONFIG.load(deps.as_ref().storage).unwrap().ticket_price;
-        let info_with_funds = mock_info(
-            "player",
-            &[Coin {
-                denom: main_denom.to_string(),
-                amount: Uint128::from(ticket_price),
-            }],
+            CONFIG.load(&deps.storage).unwrap(),
+            Config {
+                owner: Some(Addr::unchecked("creator")),
+                ticket_price: 100,
+                fee_schedule,
+                fee_recipients: owner_fee_recipients("creator"),
+                shoot_duration: 60,
+                cooldown_policy: CooldownPolicy::UntilResolved,
+                resolution_mode: ResolutionMode::RefereeQuorum,
+                goal_probability_bps: 500,
+                refund_grace_period: 600,
+                liveness_period: 3600,
+                config_timelock: 0,
+            }
         );
-
-        // execute shoot
-        let msg = ExecuteMsg::Shoot {};
-        let res = execute(deps.as_mut(), env.clone(), info_with_funds.clone(), msg).unwrap();
-        // check response
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::QueryReferees {}).unwrap();
         assert_eq!(
-            res.attributes,
-            vec![
-                attr("method", "execute_shoot"),
-                attr(
-                    "shoot_deadline",
-                    env.block.time.seconds().add(SHOOT_DURATION).to_string()
-                )
-            ]
+            from_json::<RefereesResponse>(&res).unwrap().referees,
+            vec!["referee".to_string()]
         );
 
-        // check if deadline is set
-        let res = query(
-            deps.as_ref(),
+        // fields are validated together, nothing changes if one of them is invalid
+        let err = execute(
+            deps.as_mut(),
             env.clone(),
-            QueryMsg::QueryShootDeadline {
-                address: Addr::unchecked("player".to_string()),
-            },
+            info.clone(),
+            update_config(ConfigUpdate {
+                ticket_price: Some(200),
+                goal_probability_bps: Some(10001),
+                ..Default::default()
+            }),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidGoalProbability { .. }));
+        assert_eq!(CONFIG.load(&deps.storage).unwrap().ticket_price, 100);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            update_config(ConfigUpdate {
+                refund_grace_period: Some(u64::MAX),
+                ..Default::default()
+            }),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::InvalidRefundGracePeriod { .. }
+        ));
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            update_config(ConfigUpdate::default()),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::EmptyConfigUpdate {}));
+
+        execute(
+            deps.as_mut(),
+            env,
+            info,
+            update_config(ConfigUpdate {
+                ticket_price: Some(200),
+                resolution_mode: Some(ResolutionMode::Referee),
+                goal_probability_bps: Some(1000),
+                ..Default::default()
+            }),
         )
         .unwrap();
-        let shoot_deadline: u64 = from_binary::<ShootDeadlineResponse>(&res)
-            .unwrap()
-            .shoot_deadline;
-        assert_eq!(shoot_deadline, env.block.time.seconds().add(SHOOT_DURATION));
+        let config = CONFIG.load(&deps.storage).unwrap();
+        assert_eq!(config.ticket_price, 200);
+        assert_eq!(config.resolution_mode, ResolutionMode::Referee);
+        assert_eq!(config.goal_probability_bps, 1000);
+        assert_eq!(config.shoot_duration, 60);
+        assert_eq!(config.cooldown_policy, CooldownPolicy::UntilResolved);
     }
 
     #[test]
-    fn test_shoot_10ntrn_and_goal_shot_success() {
+    fn test_bond_stays_with_operator_across_ownership() {
         let mut env = mock_env();
-        let main_denom = "usei";
-        let mut deps = mock_dependencies_with_balances(&[
-            (env.contract.address.as_str(), &[Coin::new(100, main_denom)]),
-            ("player", &[Coin::new(100, main_denom)]),
-        ]);
-
+        let mut deps = mock_dependencies();
         let info = mock_info("creator", &[]);
         let msg = InstantiateMsg {
-            owner: Some("creator".to_string()),
-            main_denom: main_denom.to_string(),
-        };
-
-        let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
-        assert_eq!(0, res.messages.len());
-
-        // set ticket price
-        let msg = ExecuteMsg::SetTicketPrice {
-            new_ticket_price: 100,
+            owner: None,
+            main_denom: "usei".to_string(),
+            ..Default::default()
         };
-        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
-        assert_eq!(
-            res.attributes,
-            vec![
-                attr("method", "execute_set_ticket_price"),
-                attr("new_ticket_price", "100")
-            ]
-        );
-
-        let ticket_price = CONFIG.load(deps.as_ref().storage).unwrap().ticket_price;
-        let info_with_funds = mock_info(
-            "player",
-            &[Coin {
-                denom: main_denom.to_string(),
-                amount: Uint128::from(ticket_price),
-            }],
-        );
-
-        // execute shoot
-        let msg = ExecuteMsg::Shoot {};
-        let res = execute(deps.as_mut(), env.clone(), info_with_funds.clone(), msg).unwrap();
-        // check response
-        assert_eq!(
-            res.attributes,
-            vec![
-                attr("method", "execute_shoot"),
-                attr(
-                    "shoot_deadline",
-                    env.block.time.seconds().add(SHOOT_DURATION).to_string()
-                )
-            ]
-        );
-
-        // check if deadline is set
-        let res = query(
-            deps.as_ref(),
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        execute(
+            deps.as_mut(),
             env.clone(),
-            QueryMsg::QueryShootDeadline {
-                address: Addr::unchecked("player".to_string()),
-            },
+            mock_info("creator", &[Coin::new(1000, "usei")]),
+            ExecuteMsg::Bond {},
         )
         .unwrap();
-        let shoot_deadline: u64 = from_binary::<ShootDeadlineResponse>(&res)
-            .unwrap()
-            .shoot_deadline;
-        assert_eq!(shoot_deadline, env.block.time.seconds().add(SHOOT_DURATION));
-
-        env.block.time = Timestamp::from_seconds(env.block.time.seconds() + 50);
-
-        // calculate reward should be 80% of the contract balance
-        let res = query(deps.as_ref(), env.clone(), QueryMsg::QueryBalance {}).unwrap();
-        let contract_balance: ContractBalanceResponse = from_binary(&res).unwrap();
-        let reward_amount = contract_balance.amount * 80 / 100;
-        let admin_amount = contract_balance.amount * 4 / 100;
+        for (sender, msg) in [
+            (
+                "creator",
+                ExecuteMsg::ProposeNewOwner {
+                    address: "bob".to_string(),
+                    expiry: None,
+                },
+            ),
+            ("bob", ExecuteMsg::AcceptOwnership {}),
+        ] {
+            execute(deps.as_mut(), env.clone(), mock_info(sender, &[]), msg).unwrap();
+        }
 
-        // goal shot
-        let msg = ExecuteMsg::GoalShot {
-            player_address: Addr::unchecked("player".to_string()),
-        };
-        let info = mock_info("creator", &[]);
-        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        // the ownership change starts unbonding the previous operator's bond
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::QueryBond {}).unwrap();
+        let bond = from_json::<BondResponse>(&res).unwrap();
+        assert_eq!(bond.operator, Some("creator".to_string()));
+        assert_eq!((bond.bonded, bond.unbonding), (0, 1000));
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("bob", &[Coin::new(10, "usei")]),
+            ExecuteMsg::Bond {},
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::BondHeldByOperator { .. }));
 
-        // check response
+        env.block.time = env.block.time.plus_seconds(DEFAULT_UNBONDING_PERIOD);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("bob", &[]),
+            ExecuteMsg::WithdrawBond {},
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            ExecuteMsg::WithdrawBond {},
+        )
+        .unwrap();
         assert_eq!(
-            res.attributes,
-            vec![
-                attr("method", "goal_shot"),
-                attr("pre_balance", contract_balance.amount.to_string()),
-                attr("reward_transfer_to_admin", admin_amount.to_string()),
-                attr("reward_transfer", reward_amount.to_string()),
-            ]
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "creator".to_string(),
+                amount: vec![Coin::new(1000, "usei")],
+            })
         );
+
+        // the new owner can post a bond of their own now
+        execute(
+            deps.as_mut(),
+            env,
+            mock_info("bob", &[Coin::new(10, "usei")]),
+            ExecuteMsg::Bond {},
+        )
+        .unwrap();
     }
 }
@@ -9,6 +9,9 @@ pub enum ContractError {
     #[error("Unauthorized")]
     Unauthorized {},
 
+    #[error("Unauthorized: missing role {role}")]
+    MissingRole { role: String },
+
     #[error("Custom Error val: {val:?}")]
     CustomError { val: String },
     // Add any other custom errors you like here.
@@ -35,4 +38,187 @@ pub enum ContractError {
 
     #[error("Player not joined")]
     PlayerNotJoined {},
+
+    #[error("Fee schedule adds up to {total_bps} bps, more than 10000 bps (100%)")]
+    FeeScheduleTooHigh { total_bps: u32 },
+
+    #[error("Ticket fee of {ticket_fee_bps} bps is more than 10000 bps (100%)")]
+    TicketFeeTooHigh { ticket_fee_bps: u16 },
+
+    #[error("Fee recipient weights add up to {total_bps} bps, expected 10000 bps")]
+    FeeRecipientWeights { total_bps: u32 },
+
+    #[error("Too many fee recipients, at most {max} allowed")]
+    TooManyFeeRecipients { max: u32 },
+
+    #[error("Shoot cooldown active until {available_at}")]
+    ShootCooldownActive { available_at: u64 },
+
+    #[error("Invalid shoot duration {actual}s, must be between {min}s and {max}s")]
+    InvalidShootDuration { min: u64, max: u64, actual: u64 },
+
+    #[error("Shot {shot_id} must be resolved by {mode}")]
+    WrongResolutionMode { shot_id: u64, mode: String },
+
+    #[error("Invalid goal probability {goal_probability_bps} bps, must be at most 10000 bps")]
+    InvalidGoalProbability { goal_probability_bps: u16 },
+
+    #[error("Client seed is required, at most {max_len} bytes")]
+    InvalidClientSeed { max_len: u32 },
+
+    #[error("Seed commitment must be a 32 byte sha256 hash")]
+    InvalidSeedCommitment {},
+
+    #[error("No seed commitment available")]
+    NoSeedCommitment {},
+
+    #[error("Revealed seed does not match the commitment of shot {shot_id}")]
+    InvalidReveal { shot_id: u64 },
+
+    #[error("Drand is not configured")]
+    DrandNotConfigured {},
+
+    #[error("Invalid drand config: {reason}")]
+    InvalidDrandConfig { reason: String },
+
+    #[error("Drand round {round} is only published after the shoot deadline")]
+    DrandRoundTooLate { round: u64 },
+
+    #[error("Invalid drand beacon for round {round}")]
+    InvalidDrandBeacon { round: u64 },
+
+    #[error("Randomness proxy is not configured")]
+    RandomnessProxyNotConfigured {},
+
+    #[error("Unknown randomness job {job_id}")]
+    UnknownRandomnessJob { job_id: String },
+
+    #[error("Randomness must be 32 bytes")]
+    InvalidRandomness {},
+
+    #[error("Invalid attestation key")]
+    InvalidAttestationKey {},
+
+    #[error("No attestation key configured")]
+    AttestationKeyNotConfigured {},
+
+    #[error("Attestation of shot {shot_id} expired")]
+    AttestationExpired { shot_id: u64 },
+
+    #[error("Invalid attestation of shot {shot_id}")]
+    InvalidAttestation { shot_id: u64 },
+
+    #[error("Referee quorum is not configured")]
+    RefereeQuorumNotConfigured {},
+
+    #[error("Invalid referee threshold {threshold} for {referees} referees")]
+    InvalidRefereeThreshold { threshold: u32, referees: u32 },
+
+    #[error("Config update sets no field")]
+    EmptyConfigUpdate {},
+
+    #[error("Config change {change_id} not found")]
+    ConfigChangeNotFound { change_id: u64 },
+
+    #[error("Config change {change_id} can be applied from {executable_at}")]
+    ConfigChangeNotReady { change_id: u64, executable_at: u64 },
+
+    #[error("Config change {change_id} expired at {expires_at}")]
+    ConfigChangeExpired { change_id: u64, expires_at: u64 },
+
+    #[error("Invalid config timelock, max {max}")]
+    InvalidConfigTimelock { max: u64 },
+
+    #[error("{what} is paused")]
+    Paused { what: String },
+
+    #[error("{what} is not paused")]
+    NotPaused { what: String },
+
+    #[error("{address} already holds role {role}")]
+    RoleAlreadyGranted { address: String, role: String },
+
+    #[error("{address} does not hold role {role}")]
+    RoleNotGranted { address: String, role: String },
+
+    #[error("Already voted for shot {shot_id}")]
+    AlreadyVoted { shot_id: u64 },
+
+    #[error("No settlement held for shot {shot_id}")]
+    SettlementNotFound { shot_id: u64 },
+
+    #[error("Dispute window of shot {shot_id} closed at {release_at}")]
+    DisputeWindowClosed { shot_id: u64, release_at: u64 },
+
+    #[error("Dispute window of shot {shot_id} open until {release_at}")]
+    DisputeWindowOpen { shot_id: u64, release_at: u64 },
+
+    #[error("Shot {shot_id} is disputed")]
+    ShotDisputed { shot_id: u64 },
+
+    #[error("Shot {shot_id} is not disputed")]
+    ShotNotDisputed { shot_id: u64 },
+
+    #[error("A kick with direction and curve in [-1000, 1000] and power in [0, 1000] is required")]
+    InvalidKick {},
+
+    #[error("Invalid slash share {slash_bps} bps, must be at most 10000 bps")]
+    InvalidSlashShare { slash_bps: u16 },
+
+    #[error("Invalid unbonding period, max {max}")]
+    InvalidUnbondingPeriod { max: u64 },
+
+    #[error("Bond is still unbonding until {release_at}")]
+    BondUnbonding { release_at: u64 },
+
+    #[error("The bond of operator {operator} has to be withdrawn first")]
+    BondHeldByOperator { operator: String },
+
+    #[error("Operator bond of {available} does not cover the prize of {required}")]
+    InsufficientBond { required: u128, available: u128 },
+
+    #[error("The guardian cannot be changed or removed while a dispute is pending")]
+    GuardianLocked {},
+
+    #[error("Bond cannot be withdrawn while a goal can still be disputed")]
+    DisputePending {},
+
+    #[error("Shot {shot_id} can be refunded from {available_at}")]
+    RefundNotAvailable { shot_id: u64, available_at: u64 },
+
+    #[error("Shot {shot_id} is resolved by {mode} and cannot be refunded")]
+    RefundNotAllowed { shot_id: u64, mode: String },
+
+    #[error("Invalid refund grace period, max {max}")]
+    InvalidRefundGracePeriod { max: u64 },
+
+    #[error("Invalid dispute period, max {max}")]
+    InvalidDisputePeriod { max: u64 },
+
+    #[error("Contract is in degraded mode, the referee missed the liveness period")]
+    DegradedMode {},
+
+    #[error("Invalid liveness period, min {min}")]
+    InvalidLivenessPeriod { min: u64 },
+
+    #[error("{pending} shots are still pending")]
+    BacklogNotResolved { pending: u32 },
+
+    #[error("No ownership transfer is pending")]
+    NoPendingOwner {},
+
+    #[error("Ownership proposal expired at {expires_at}")]
+    OwnershipProposalExpired { expires_at: u64 },
+
+    #[error("Expiry must be in the future")]
+    InvalidExpiry {},
+
+    #[error("Nothing to claim")]
+    NothingToClaim {},
+
+    #[error("Shot {shot_id} not found")]
+    ShotNotFound { shot_id: u64 },
+
+    #[error("Shot {shot_id} already resolved as {status}")]
+    ShotAlreadyResolved { shot_id: u64, status: String },
 }
@@ -0,0 +1,198 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    coins, to_json_binary, Addr, Binary, Coin, Deps, DepsMut, Empty, Env, HexBinary, MessageInfo,
+    Response, StdError, StdResult, WasmMsg,
+};
+use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+use cw_storage_plus::Item;
+
+use crate::contract::{execute, instantiate, query, reply};
+use crate::msg::{
+    ClaimableResponse, ExecuteMsg, InstantiateMsg, NoisCallback, QueryMsg, ShotResponse,
+};
+use crate::state::{ResolutionMode, ShotResolution, ShotStatus};
+use crate::ContractError;
+
+const MAIN_DENOM: &str = "usei";
+
+// Mock of a randomness proxy, keeps the last request and delivers randomness on demand
+#[cw_serde]
+enum MockProxyMsg {
+    GetNextRandomness { job_id: String },
+    Deliver { randomness: HexBinary },
+}
+
+const LAST_JOB: Item<(Addr, String)> = Item::new("last_job");
+
+fn mock_proxy_instantiate(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+fn mock_proxy_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: MockProxyMsg,
+) -> StdResult<Response> {
+    match msg {
+        MockProxyMsg::GetNextRandomness { job_id } => {
+            LAST_JOB.save(deps.storage, &(info.sender, job_id))?;
+            Ok(Response::new())
+        }
+        MockProxyMsg::Deliver { randomness } => {
+            let (requester, job_id) = LAST_JOB.load(deps.storage)?;
+            let callback = ExecuteMsg::NoisReceive {
+                callback: NoisCallback {
+                    job_id,
+                    published: env.block.time,
+                    randomness,
+                },
+            };
+            Ok(Response::new().add_message(WasmMsg::Execute {
+                contract_addr: requester.to_string(),
+                msg: to_json_binary(&callback)?,
+                funds: vec![],
+            }))
+        }
+    }
+}
+
+fn mock_proxy_query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+    Err(StdError::generic_err("no queries"))
+}
+
+fn mock_proxy() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(
+        mock_proxy_execute,
+        mock_proxy_instantiate,
+        mock_proxy_query,
+    ))
+}
+
+fn jackpot() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(execute, instantiate, query).with_reply(reply))
+}
+
+#[test]
+fn test_randomness_proxy_shot() {
+    let owner = Addr::unchecked("owner");
+    let player = Addr::unchecked("player");
+    let mut app = App::new(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &player, coins(1000, MAIN_DENOM))
+            .unwrap();
+    });
+
+    let proxy_code = app.store_code(mock_proxy());
+    let proxy = app
+        .instantiate_contract(proxy_code, owner.clone(), &Empty {}, &[], "proxy", None)
+        .unwrap();
+    let jackpot_code = app.store_code(jackpot());
+    let jackpot = app
+        .instantiate_contract(
+            jackpot_code,
+            owner.clone(),
+            &InstantiateMsg {
+                owner: None,
+                main_denom: MAIN_DENOM.to_string(),
+                ticket_price: Some(100),
+                resolution_mode: Some(ResolutionMode::RandomnessProxy),
+                goal_probability_bps: Some(10000),
+                ..Default::default()
+            },
+            &[],
+            "jackpot",
+            None,
+        )
+        .unwrap();
+
+    app.execute_contract(
+        owner.clone(),
+        jackpot.clone(),
+        &ExecuteMsg::SetRandomnessProxy {
+            new_randomness_proxy: proxy.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        player.clone(),
+        jackpot.clone(),
+        &ExecuteMsg::Shoot {
+            client_seed: None,
+            kick: None,
+        },
+        &[Coin::new(100, MAIN_DENOM)],
+    )
+    .unwrap();
+    let shot: ShotResponse = app
+        .wrap()
+        .query_wasm_smart(&jackpot, &QueryMsg::QueryShot { shot_id: 1 })
+        .unwrap();
+    assert_eq!(shot.status, ShotStatus::Pending);
+    assert_eq!(
+        shot.resolution,
+        ShotResolution::RandomnessProxy {
+            job_id: "shot-1".to_string(),
+            goal_probability_bps: 10000,
+        }
+    );
+
+    // only the proxy can deliver randomness
+    let err = app
+        .execute_contract(
+            player.clone(),
+            jackpot.clone(),
+            &ExecuteMsg::NoisReceive {
+                callback: NoisCallback {
+                    job_id: "shot-1".to_string(),
+                    published: app.block_info().time,
+                    randomness: HexBinary::from(vec![0u8; 32]),
+                },
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    ));
+
+    app.execute_contract(
+        owner,
+        proxy,
+        &MockProxyMsg::Deliver {
+            randomness: HexBinary::from(vec![7u8; 32]),
+        },
+        &[],
+    )
+    .unwrap();
+    let shot: ShotResponse = app
+        .wrap()
+        .query_wasm_smart(&jackpot, &QueryMsg::QueryShot { shot_id: 1 })
+        .unwrap();
+    assert_eq!(shot.status, ShotStatus::Goal);
+
+    // the prize is 80% of the jackpot
+    let claimable: ClaimableResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &jackpot,
+            &QueryMsg::QueryClaimable {
+                address: player.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(claimable.amount, 80);
+    app.execute_contract(player.clone(), jackpot, &ExecuteMsg::Claim {}, &[])
+        .unwrap();
+    let balance = app.wrap().query_balance(&player, MAIN_DENOM).unwrap();
+    assert_eq!(balance.amount.u128(), 980);
+}
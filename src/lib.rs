@@ -1,6 +1,10 @@
 pub mod contract;
 mod error;
+#[cfg(test)]
+mod integration_tests;
 pub mod msg;
+pub mod physics;
+mod randomness;
 pub mod state;
 
 pub use crate::error::ContractError;
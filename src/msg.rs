@@ -1,19 +1,212 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, HexBinary, Timestamp};
 
+use crate::physics::Kick;
+use crate::state::{
+    AttestationKey, BondConfig, ConfigChange, CooldownPolicy, DisputeConfig, DrandConfig,
+    FeeRecipient, FeeSchedule, PauseTarget, PoolBucket, ResolutionMode, Role, Settlement,
+    ShotResolution, ShotStatus,
+};
+
+// Unset settings take their defaults
 #[cw_serde]
+#[derive(Default)]
 pub struct InstantiateMsg {
     pub owner: Option<String>,
     pub main_denom: String,
+    pub ticket_price: Option<u128>,
+    pub fee_schedule: Option<FeeSchedule>,
+    // defaults to the whole admin fee going to the owner
+    pub fee_recipients: Option<Vec<FeeRecipient>>,
+    pub shoot_duration: Option<u64>,
+    pub cooldown_policy: Option<CooldownPolicy>,
+    pub resolution_mode: Option<ResolutionMode>,
+    pub goal_probability_bps: Option<u16>,
+    pub refund_grace_period: Option<u64>,
+    pub liveness_period: Option<u64>,
+    pub config_timelock: Option<u64>,
+    // the owner is only implicitly an admin, every other role has to be granted
+    pub roles: Option<Vec<RoleGrant>>,
+}
+
+#[cw_serde]
+pub struct RoleGrant {
+    pub address: String,
+    pub role: Role,
 }
 
 #[cw_serde]
 pub enum ExecuteMsg {
-    Shoot {},
-    GoalShot { player_address: Addr },
-    SetTicketPrice { new_ticket_price: u128 },
-    SetRewardPercentage { new_reward_percentage: u8 },
-    SetAdminPercentage { new_admin_percentage: u8 },
+    Shoot {
+        client_seed: Option<String>,
+        // required in `Physics` resolution mode
+        kick: Option<Kick>,
+    },
+    // Sent by a referee, or by anyone with an attestation of the result
+    GoalShot {
+        shot_id: u64,
+        attestation: Option<ResultAttestation>,
+    },
+    MissShot {
+        shot_id: u64,
+        attestation: Option<ResultAttestation>,
+    },
+    VoteGoal {
+        shot_id: u64,
+    },
+    Dispute {
+        shot_id: u64,
+    },
+    // Uphold or void a disputed goal, guardian only
+    ResolveDispute {
+        shot_id: u64,
+        uphold: bool,
+    },
+    Finalize {
+        shot_id: u64,
+    },
+    // Posted by the owner, unbonded and withdrawn by the address that posted it
+    Bond {},
+    Unbond {
+        amount: u128,
+    },
+    WithdrawBond {},
+    // sha256 of operator seeds, physics seeds carry the keeper position in their first two bytes
+    CommitSeeds {
+        commitments: Vec<HexBinary>,
+    },
+    // `signature` is the drand beacon of the round the shot is bound to
+    RevealShot {
+        shot_id: u64,
+        server_seed: HexBinary,
+        signature: HexBinary,
+    },
+    ResolveDrandShot {
+        shot_id: u64,
+        signature: HexBinary,
+    },
+    // Callback of the randomness proxy for a job requested at `Shoot`
+    NoisReceive {
+        callback: NoisCallback,
+    },
+    ExpireShots {
+        limit: Option<u32>,
+    },
+    RefundShot {
+        shot_id: u64,
+    },
+    // Anyone can record that the referee missed the liveness period
+    CheckLiveness {},
+    ResumeNormalMode {},
+    SyncBalance {
+        bucket: PoolBucket,
+    },
+    Claim {},
+    WithdrawFees {
+        amount: u128,
+        recipient: Option<String>,
+    },
+    // Move reserve funds back into the jackpot, or pay them out to `recipient`, treasury only
+    ReleaseReserve {
+        amount: u128,
+        recipient: Option<String>,
+    },
+    // Engage a switch, pauser only
+    Pause {
+        what: PauseTarget,
+    },
+    // Release a switch now, or schedule its release at `unpause_at`, admin only
+    Unpause {
+        what: PauseTarget,
+        unpause_at: Option<u64>,
+    },
+    ProposeNewOwner {
+        address: String,
+        // unix time in seconds after which the proposal can no longer be accepted
+        expiry: Option<u64>,
+    },
+    AcceptOwnership {},
+    CancelOwnershipProposal {},
+    RenounceOwnership {},
+    // Unset fields are left unchanged, the set ones are validated together
+    UpdateConfig {
+        ticket_price: Option<u128>,
+        fee_schedule: Option<FeeSchedule>,
+        fee_recipients: Option<Vec<FeeRecipient>>,
+        shoot_duration: Option<u64>,
+        cooldown_policy: Option<CooldownPolicy>,
+        resolution_mode: Option<ResolutionMode>,
+        goal_probability_bps: Option<u16>,
+        refund_grace_period: Option<u64>,
+        liveness_period: Option<u64>,
+        // shortening the timelock has to wait for the current one
+        config_timelock: Option<u64>,
+    },
+    SetDrandConfig {
+        new_drand_config: DrandConfig,
+    },
+    SetRandomnessProxy {
+        new_randomness_proxy: String,
+    },
+    SetAttestationKey {
+        new_attestation_key: Option<AttestationKey>,
+    },
+    GrantRole {
+        address: String,
+        role: Role,
+    },
+    RevokeRole {
+        address: String,
+        role: Role,
+    },
+    SetRefereeThreshold {
+        new_threshold: u32,
+    },
+    SetDisputeConfig {
+        new_dispute_config: Option<DisputeConfig>,
+    },
+    SetBondConfig {
+        new_bond_config: BondConfig,
+    },
+    // Apply a queued config change once its timelock passed and before it lapses,
+    // callable by anyone
+    ApplyConfigChange {
+        change_id: u64,
+    },
+    CancelConfigChange {
+        change_id: u64,
+    },
+}
+
+// Signature of the attestation key over the sha256 hash of the JSON encoded
+// `AttestationPayload` of the shot
+#[cw_serde]
+pub struct ResultAttestation {
+    pub expires_at: u64,
+    pub signature: HexBinary,
+}
+
+#[cw_serde]
+pub struct AttestationPayload {
+    pub contract: String,
+    pub shot_id: u64,
+    pub player: String,
+    pub outcome: ShotStatus,
+    pub expires_at: u64,
+}
+
+// Randomness delivered by the proxy, same shape as the Nois callback
+#[cw_serde]
+pub struct NoisCallback {
+    pub job_id: String,
+    pub published: Timestamp,
+    pub randomness: HexBinary,
+}
+
+// Request sent to the randomness proxy
+#[cw_serde]
+pub enum ProxyExecuteMsg {
+    GetNextRandomness { job_id: String },
 }
 
 #[cw_serde]
@@ -21,20 +214,88 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(ConfigResponse)]
     QueryConfig {},
+    #[returns(PendingOwnerResponse)]
+    QueryPendingOwner {},
+    #[returns(ConfigChangesResponse)]
+    QueryConfigChanges {},
     #[returns(ShootDeadlineResponse)]
     QueryShootDeadline { address: Addr },
     #[returns(ContractBalanceResponse)]
     QueryBalance {},
+    #[returns(ShotResponse)]
+    QueryShot { shot_id: u64 },
+    #[returns(SeedCommitmentResponse)]
+    QuerySeedCommitment { commitment_id: u64 },
+    #[returns(DrandConfigResponse)]
+    QueryDrandConfig {},
+    #[returns(RandomnessProxyResponse)]
+    QueryRandomnessProxy {},
+    #[returns(AttestationKeyResponse)]
+    QueryAttestationKey {},
+    #[returns(RolesResponse)]
+    QueryRoles {},
+    #[returns(PausesResponse)]
+    QueryPauses {},
+    #[returns(RefereesResponse)]
+    QueryReferees {},
+    #[returns(GoalVotesResponse)]
+    QueryGoalVotes { shot_id: u64 },
+    #[returns(DisputeConfigResponse)]
+    QueryDisputeConfig {},
+    #[returns(SettlementResponse)]
+    QuerySettlement { shot_id: u64 },
+    #[returns(BondResponse)]
+    QueryBond {},
+    #[returns(SlashesResponse)]
+    QuerySlashes {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(LivenessResponse)]
+    QueryLiveness {},
+    #[returns(LedgerResponse)]
+    QueryLedger {},
+    #[returns(FeesResponse)]
+    QueryFees {},
+    #[returns(ClaimableResponse)]
+    QueryClaimable { address: Addr },
 }
 
 // We define a custom struct for each query response
 #[cw_serde]
 pub struct ConfigResponse {
-    pub owner: String,
+    pub owner: Option<String>,
     pub ticket_price: u128,
-    pub reward_percentage: u8,
-    pub admin_percentage: u8,
-    pub shoot_duration: u8,
+    pub fee_schedule: FeeSchedule,
+    pub fee_recipients: Vec<FeeRecipient>,
+    pub shoot_duration: u64,
+    pub cooldown_policy: CooldownPolicy,
+    pub resolution_mode: ResolutionMode,
+    pub goal_probability_bps: u16,
+    pub refund_grace_period: u64,
+    pub liveness_period: u64,
+    pub config_timelock: u64,
+}
+
+#[cw_serde]
+pub struct PendingOwnerResponse {
+    pub pending_owner: Option<String>,
+    pub expires_at: Option<u64>,
+}
+
+#[cw_serde]
+pub struct ConfigChangeResponse {
+    pub change_id: u64,
+    pub change: ConfigChange,
+    pub queued_at: u64,
+    pub executable_at: u64,
+    pub expires_at: u64,
+}
+
+#[cw_serde]
+pub struct ConfigChangesResponse {
+    pub config_timelock: u64,
+    pub changes: Vec<ConfigChangeResponse>,
 }
 
 #[cw_serde]
@@ -46,3 +307,154 @@ pub struct ShootDeadlineResponse {
 pub struct ContractBalanceResponse {
     pub amount: u128,
 }
+
+#[cw_serde]
+pub struct DrandConfigResponse {
+    pub drand: Option<DrandConfig>,
+}
+
+#[cw_serde]
+pub struct RandomnessProxyResponse {
+    pub randomness_proxy: Option<String>,
+}
+
+#[cw_serde]
+pub struct AttestationKeyResponse {
+    pub attestation_key: Option<AttestationKey>,
+}
+
+#[cw_serde]
+pub struct RoleHolders {
+    pub role: Role,
+    pub addresses: Vec<String>,
+}
+
+#[cw_serde]
+pub struct RolesResponse {
+    // holds the admin role without being listed
+    pub owner: Option<String>,
+    pub roles: Vec<RoleHolders>,
+}
+
+#[cw_serde]
+pub struct PauseResponse {
+    pub what: PauseTarget,
+    pub paused: bool,
+    pub paused_at: Option<u64>,
+    pub unpause_at: Option<u64>,
+}
+
+#[cw_serde]
+pub struct PausesResponse {
+    pub pauses: Vec<PauseResponse>,
+}
+
+#[cw_serde]
+pub struct RefereesResponse {
+    pub referees: Vec<String>,
+    pub threshold: u32,
+}
+
+#[cw_serde]
+pub struct GoalVote {
+    pub referee: String,
+    pub voted_at: u64,
+}
+
+#[cw_serde]
+pub struct GoalVotesResponse {
+    pub shot_id: u64,
+    pub votes: Vec<GoalVote>,
+}
+
+#[cw_serde]
+pub struct DisputeConfigResponse {
+    pub dispute_config: Option<DisputeConfig>,
+}
+
+#[cw_serde]
+pub struct SettlementResponse {
+    pub shot_id: u64,
+    pub settlement: Option<Settlement>,
+}
+
+#[cw_serde]
+pub struct BondResponse {
+    pub operator: Option<String>,
+    pub bonded: u128,
+    pub locked: u128,
+    pub unbonding: u128,
+    pub unbonding_release_at: u64,
+    pub total_slashed: u128,
+    pub bond_config: BondConfig,
+}
+
+#[cw_serde]
+pub struct SlashResponse {
+    pub slash_id: u64,
+    pub shot_id: u64,
+    pub amount: u128,
+    pub reason: String,
+    pub timestamp: u64,
+}
+
+#[cw_serde]
+pub struct SlashesResponse {
+    pub slashes: Vec<SlashResponse>,
+}
+
+#[cw_serde]
+pub struct LivenessResponse {
+    pub degraded: bool,
+    pub awaiting_since: Option<u64>,
+    pub liveness_period: u64,
+}
+
+#[cw_serde]
+pub struct LedgerResponse {
+    pub jackpot: u128,
+    pub admin_fees: u128,
+    pub reserve: u128,
+    pub claimable: u128,
+    pub settling: u128,
+    pub bond: u128,
+    pub admin_dust: u128,
+    pub reserve_dust: u128,
+    // funds held by the contract that are not in any bucket yet
+    pub unaccounted: u128,
+}
+
+#[cw_serde]
+pub struct FeesResponse {
+    pub ticket_fee_bps: u16,
+    pub available: u128,
+    pub total_accrued: u128,
+    pub total_withdrawn: u128,
+}
+
+#[cw_serde]
+pub struct ClaimableResponse {
+    pub amount: u128,
+}
+
+#[cw_serde]
+pub struct ShotResponse {
+    pub id: u64,
+    pub player: String,
+    pub paid_amount: u128,
+    pub started_at: u64,
+    pub duration: u64,
+    pub deadline: u64,
+    pub status: ShotStatus,
+    pub jackpot_basis: u128,
+    pub reward_amount: u128,
+    pub resolution: ShotResolution,
+}
+
+#[cw_serde]
+pub struct SeedCommitmentResponse {
+    pub commitment_id: u64,
+    pub commitment: HexBinary,
+    pub shot_id: Option<u64>,
+    pub server_seed: Option<HexBinary>,
+}
@@ -0,0 +1,87 @@
+use cosmwasm_schema::cw_serde;
+
+// Fixed-point shot model. Lateral positions are in 1/1000 of half the goal width,
+// so the posts stand at -1000 and 1000.
+//
+// The kick is scored against the keeper position the operator committed before the
+// shot. A kick that beats the keeper still has to be finished: it only goes in with
+// the goal probability configured for the shot, so no kick scores more often than
+// `goal_probability_bps`, and a kick the keeper reaches never scores.
+pub const POST: i32 = 1000;
+// Lateral drift of a full curve at the goal line
+const MAX_CURVE_DRIFT: i32 = 300;
+// Weaker kicks roll into the keeper's hands, stronger ones fly over the bar
+const MIN_POWER: i32 = 300;
+const MAX_POWER: i32 = 900;
+// Reach of the keeper against the slowest kick that gets to the goal
+const KEEPER_REACH: i32 = 400;
+
+// Kick parameters chosen by the player
+#[cw_serde]
+pub struct Kick {
+    // aim at the goal line, from -1000 (left post) to 1000 (right post)
+    pub direction: i16,
+    // from 0 to 1000
+    pub power: u16,
+    // sideways spin, from -1000 to 1000
+    pub curve: i16,
+}
+
+impl Kick {
+    pub fn is_valid(&self) -> bool {
+        (-POST..=POST).contains(&(self.direction as i32))
+            && self.power as i32 <= 1000
+            && (-1000..=1000).contains(&(self.curve as i32))
+    }
+}
+
+#[cw_serde]
+pub enum KickResult {
+    Goal,
+    Saved,
+    Wide,
+    OverTheBar,
+    TooWeak,
+}
+
+impl KickResult {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KickResult::Goal => "goal",
+            KickResult::Saved => "saved",
+            KickResult::Wide => "wide",
+            KickResult::OverTheBar => "over_the_bar",
+            KickResult::TooWeak => "too_weak",
+        }
+    }
+}
+
+// Where the ball crosses the goal line
+pub fn ball_position(kick: &Kick) -> i32 {
+    kick.direction as i32 + kick.curve as i32 * MAX_CURVE_DRIFT / 1000
+}
+
+// Harder kicks leave the keeper less time, shrinking the reach down to half
+pub fn keeper_reach(kick: &Kick) -> i32 {
+    let power = (kick.power as i32).clamp(MIN_POWER, MAX_POWER);
+    KEEPER_REACH - KEEPER_REACH * (power - MIN_POWER) / (2 * (MAX_POWER - MIN_POWER))
+}
+
+// Score a kick against a keeper standing at `keeper_position`
+pub fn simulate(kick: &Kick, keeper_position: i32) -> KickResult {
+    let power = kick.power as i32;
+    if power < MIN_POWER {
+        return KickResult::TooWeak;
+    }
+    if power > MAX_POWER {
+        return KickResult::OverTheBar;
+    }
+    let ball = ball_position(kick);
+    if ball.abs() >= POST {
+        return KickResult::Wide;
+    }
+    if (ball - keeper_position).abs() <= keeper_reach(kick) {
+        return KickResult::Saved;
+    }
+    KickResult::Goal
+}
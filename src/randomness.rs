@@ -0,0 +1,58 @@
+use drand_verify::derive_randomness;
+use sha2::{Digest, Sha256};
+
+use crate::physics::POST;
+use crate::state::BPS_DENOMINATOR;
+
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
+// Randomness of a commit-reveal shot, mixes the operator and the player seeds with
+// a drand beacon published after the shot, so neither side can predict it
+pub fn commit_reveal_randomness(
+    server_seed: &[u8],
+    client_seed: &str,
+    shot_id: u64,
+    signature: &[u8],
+) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(server_seed);
+    hasher.update(client_seed.as_bytes());
+    hasher.update(shot_id.to_be_bytes());
+    hasher.update(derive_randomness(signature));
+    hasher.finalize().into()
+}
+
+// Randomness of a drand shot, the beacon randomness is mixed with the shot id
+// so shots bound to the same round do not share their outcome
+pub fn drand_shot_randomness(signature: &[u8], shot_id: u64) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(derive_randomness(signature));
+    hasher.update(shot_id.to_be_bytes());
+    hasher.finalize().into()
+}
+
+// Roll in [0, 10000) taken from the first 8 bytes of the randomness
+pub fn roll_bps(randomness: &[u8; 32]) -> u16 {
+    let mut head = [0u8; 8];
+    head.copy_from_slice(&randomness[..8]);
+    (u64::from_be_bytes(head) % BPS_DENOMINATOR as u64) as u16
+}
+
+// Randomness of the finish of a physics shot, the revealed operator seed mixed with a
+// drand beacon published after the kick
+pub fn physics_shot_randomness(server_seed: &[u8], shot_id: u64, signature: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(server_seed);
+    hasher.update(shot_id.to_be_bytes());
+    hasher.update(derive_randomness(signature));
+    hasher.finalize().into()
+}
+
+// Keeper position of a physics shot, committed by the operator as the first two bytes
+// of its seed and folded between the posts
+pub fn keeper_position(server_seed: &[u8]) -> Option<i32> {
+    let head: [u8; 2] = server_seed.get(..2)?.try_into().ok()?;
+    Some((u16::from_be_bytes(head) as i32) % (2 * POST + 1) - POST)
+}
@@ -1,16 +1,570 @@
 use cw_storage_plus::{Item, Map};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Empty, HexBinary};
+
+use crate::physics::Kick;
 
 #[cw_serde]
 pub struct Config {
-    pub owner: Addr,
+    // none once ownership is renounced
+    pub owner: Option<Addr>,
     pub ticket_price: u128,
-    pub reward_percentage: u8,
-    pub admin_percentage: u8,
-    pub shoot_duration: u8,
+    pub fee_schedule: FeeSchedule,
+    pub fee_recipients: Vec<FeeRecipient>,
+    pub shoot_duration: u64,
+    pub cooldown_policy: CooldownPolicy,
+    pub resolution_mode: ResolutionMode,
+    // chance of a goal for randomness based modes, in basis points
+    pub goal_probability_bps: u16,
+    // time after the shoot deadline from which an unresolved shot can be refunded
+    pub refund_grace_period: u64,
+    // the contract degrades when shots wait this long for a resolution
+    pub liveness_period: u64,
+    // delay before a queued `ConfigChange` can be applied, zero applies it right away
+    pub config_timelock: u64,
+}
+
+// Settings a player relies on when shooting, unset fields are left unchanged
+#[cw_serde]
+#[derive(Default)]
+pub struct ConfigUpdate {
+    pub ticket_price: Option<u128>,
+    pub fee_schedule: Option<FeeSchedule>,
+    pub fee_recipients: Option<Vec<FeeRecipient>>,
+    pub shoot_duration: Option<u64>,
+    pub cooldown_policy: Option<CooldownPolicy>,
+    pub resolution_mode: Option<ResolutionMode>,
+    pub goal_probability_bps: Option<u16>,
+    pub refund_grace_period: Option<u64>,
+    pub liveness_period: Option<u64>,
+    pub config_timelock: Option<u64>,
+}
+
+impl ConfigUpdate {
+    pub fn is_empty(&self) -> bool {
+        *self == ConfigUpdate::default()
+    }
+
+    pub fn apply(self, config: &mut Config) {
+        if let Some(ticket_price) = self.ticket_price {
+            config.ticket_price = ticket_price;
+        }
+        if let Some(fee_schedule) = self.fee_schedule {
+            config.fee_schedule = fee_schedule;
+        }
+        if let Some(fee_recipients) = self.fee_recipients {
+            config.fee_recipients = fee_recipients;
+        }
+        if let Some(shoot_duration) = self.shoot_duration {
+            config.shoot_duration = shoot_duration;
+        }
+        if let Some(cooldown_policy) = self.cooldown_policy {
+            config.cooldown_policy = cooldown_policy;
+        }
+        if let Some(resolution_mode) = self.resolution_mode {
+            config.resolution_mode = resolution_mode;
+        }
+        if let Some(goal_probability_bps) = self.goal_probability_bps {
+            config.goal_probability_bps = goal_probability_bps;
+        }
+        if let Some(refund_grace_period) = self.refund_grace_period {
+            config.refund_grace_period = refund_grace_period;
+        }
+        if let Some(liveness_period) = self.liveness_period {
+            config.liveness_period = liveness_period;
+        }
+        if let Some(config_timelock) = self.config_timelock {
+            config.config_timelock = config_timelock;
+        }
+    }
+}
+
+// Every setting players rely on goes through the timelock, including the timelock itself.
+// Only pause switches, roles and the keys and randomness sources shots are verified
+// against stay immediate, so they can be used in an emergency.
+#[cw_serde]
+pub enum ConfigChange {
+    Update(ConfigUpdate),
+    DisputeConfig(Option<DisputeConfig>),
+    BondConfig(BondConfig),
+    RefereeThreshold(u32),
+}
+
+impl ConfigChange {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConfigChange::Update(_) => "update",
+            ConfigChange::DisputeConfig(_) => "dispute_config",
+            ConfigChange::BondConfig(_) => "bond_config",
+            ConfigChange::RefereeThreshold(_) => "referee_threshold",
+        }
+    }
+}
+
+#[cw_serde]
+pub struct PendingConfigChange {
+    pub change: ConfigChange,
+    pub queued_at: u64,
+    pub executable_at: u64,
+    // the change lapses if nobody applies it before then
+    pub expires_at: u64,
+}
+
+// Proposed owner, who has to accept the ownership from its own address
+#[cw_serde]
+pub struct PendingOwner {
+    pub address: Addr,
+    pub expires_at: Option<u64>,
+}
+
+// Privileged roles, the owner implicitly holds only `Admin`
+#[cw_serde]
+pub enum Role {
+    // changes the config and grants roles
+    Admin,
+    // resolves referee shots and commits operator seeds
+    Referee,
+    // withdraws admin fees, releases the reserve and sweeps unaccounted funds
+    Treasury,
+    // engages the pause switches, only an admin releases them
+    Pauser,
+}
+
+impl Role {
+    pub const ALL: [Role; 4] = [Role::Admin, Role::Referee, Role::Treasury, Role::Pauser];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Admin => "admin",
+            Role::Referee => "referee",
+            Role::Treasury => "treasury",
+            Role::Pauser => "pauser",
+        }
+    }
+}
+
+pub const BPS_DENOMINATOR: u128 = 10_000;
+
+// Shares of the jackpot basis paid out on a goal, in basis points.
+// `ticket_fee_bps` is taken from every ticket into the admin fee bucket instead.
+#[cw_serde]
+pub struct FeeSchedule {
+    pub reward_bps: u16,
+    pub admin_bps: u16,
+    pub reserve_bps: u16,
+    pub ticket_fee_bps: u16,
+}
+
+impl FeeSchedule {
+    pub fn total_bps(&self) -> u32 {
+        self.reward_bps as u32 + self.admin_bps as u32 + self.reserve_bps as u32
+    }
+}
+
+#[cw_serde]
+pub enum FeeDestination {
+    Address(Addr),
+    Burn,
+}
+
+// A weighted share of the admin fee, weights of all recipients add up to 10000 bps
+#[cw_serde]
+pub struct FeeRecipient {
+    pub destination: FeeDestination,
+    pub weight_bps: u16,
+}
+
+#[cw_serde]
+pub enum CooldownPolicy {
+    // Players can hold several pending shots at once
+    AllowOverlap,
+    // A new shot is blocked while the previous one is pending and before its deadline
+    UntilResolved,
+    // A new shot is blocked for a fixed number of seconds after the previous one
+    Fixed { seconds: u64 },
+}
+
+#[cw_serde]
+pub enum ResolutionMode {
+    // The owner judges the shot with `GoalShot` / `MissShot`
+    Referee,
+    // The outcome is derived from a committed operator seed and the player seed
+    CommitReveal,
+    // The outcome is derived from a drand beacon round published after the shot
+    Drand,
+    // The outcome is derived from randomness a proxy contract calls back with
+    RandomnessProxy,
+    // A goal is paid once enough referees voted for it
+    RefereeQuorum,
+    // The player's kick is simulated against a keeper committed by the operator
+    Physics,
+}
+
+impl ResolutionMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResolutionMode::Referee => "referee",
+            ResolutionMode::CommitReveal => "commit_reveal",
+            ResolutionMode::Drand => "drand",
+            ResolutionMode::RandomnessProxy => "randomness_proxy",
+            ResolutionMode::RefereeQuorum => "referee_quorum",
+            ResolutionMode::Physics => "physics",
+        }
+    }
+}
+
+// How a shot is resolved, with the data bound to it at shoot time
+#[cw_serde]
+pub enum ShotResolution {
+    Referee,
+    CommitReveal {
+        commitment_id: u64,
+        client_seed: String,
+        // drand round mixed into the outcome
+        round: u64,
+        goal_probability_bps: u16,
+        // prize locked in the operator bond until the seed is revealed
+        prize_bond: u128,
+    },
+    Drand {
+        round: u64,
+        goal_probability_bps: u16,
+    },
+    RandomnessProxy {
+        job_id: String,
+        goal_probability_bps: u16,
+    },
+    RefereeQuorum {
+        threshold: u32,
+    },
+    Physics {
+        commitment_id: u64,
+        kick: Kick,
+        // drand round mixed into the finish
+        round: u64,
+        goal_probability_bps: u16,
+        // prize locked in the operator bond until the seed is revealed
+        prize_bond: u128,
+    },
+}
+
+impl ShotResolution {
+    pub fn mode(&self) -> ResolutionMode {
+        match self {
+            ShotResolution::Referee => ResolutionMode::Referee,
+            ShotResolution::CommitReveal { .. } => ResolutionMode::CommitReveal,
+            ShotResolution::Drand { .. } => ResolutionMode::Drand,
+            ShotResolution::RandomnessProxy { .. } => ResolutionMode::RandomnessProxy,
+            ShotResolution::RefereeQuorum { .. } => ResolutionMode::RefereeQuorum,
+            ShotResolution::Physics { .. } => ResolutionMode::Physics,
+        }
+    }
+
+    // Resolved by anyone from public randomness, at any time after the shot
+    pub fn is_permissionless(&self) -> bool {
+        matches!(
+            self,
+            ShotResolution::Drand { .. } | ShotResolution::RandomnessProxy { .. }
+        )
+    }
+
+    // Prize the operator bond pays if the seed of the shot is never revealed
+    pub fn prize_bond(&self) -> Option<u128> {
+        match self {
+            ShotResolution::CommitReveal { prize_bond, .. }
+            | ShotResolution::Physics { prize_bond, .. } => Some(*prize_bond),
+            _ => None,
+        }
+    }
+}
+
+// sha256 of an operator seed, committed before the shot it is used for
+#[cw_serde]
+pub struct SeedCommitment {
+    pub commitment: HexBinary,
+    pub shot_id: Option<u64>,
+    pub server_seed: Option<HexBinary>,
+}
+
+// Tracks whether the referee keeps up with the shots
+#[cw_serde]
+#[derive(Default)]
+pub struct Liveness {
+    // time since when a resolution is due, none while no shot waits for one
+    pub awaiting_since: Option<u64>,
+    pub degraded: bool,
+}
+
+impl Liveness {
+    pub fn is_degraded(&self, now: u64, liveness_period: u64) -> bool {
+        self.degraded
+            || self
+                .awaiting_since
+                .is_some_and(|since| now >= since.saturating_add(liveness_period))
+    }
+}
+
+// Switches of the circuit breaker
+#[cw_serde]
+pub enum PauseTarget {
+    Shooting,
+    // every path that resolves or expires a shot, or releases a held payout
+    Resolutions,
+    FeeWithdrawals,
+}
+
+impl PauseTarget {
+    pub const ALL: [PauseTarget; 3] = [
+        PauseTarget::Shooting,
+        PauseTarget::Resolutions,
+        PauseTarget::FeeWithdrawals,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PauseTarget::Shooting => "shooting",
+            PauseTarget::Resolutions => "resolutions",
+            PauseTarget::FeeWithdrawals => "fee_withdrawals",
+        }
+    }
+}
+
+#[cw_serde]
+pub struct Pause {
+    pub paused_at: u64,
+    pub paused_by: Addr,
+    // the switch releases itself from this time on
+    pub unpause_at: Option<u64>,
+}
+
+impl Pause {
+    pub fn is_active(&self, now: u64) -> bool {
+        self.unpause_at.is_none_or(|unpause_at| now < unpause_at)
+    }
+}
+
+// Unchained drand network (BLS signatures on G1, e.g. quicknet) shots are bound to
+#[cw_serde]
+pub struct DrandConfig {
+    // 96 byte G2 public key of the network
+    pub pubkey: HexBinary,
+    // unix time of round 1
+    pub genesis_time: u64,
+    // seconds between rounds
+    pub period: u64,
+}
+
+impl DrandConfig {
+    // First round published strictly after `time`
+    pub fn next_round(&self, time: u64) -> u64 {
+        if time < self.genesis_time {
+            return 1;
+        }
+        (time - self.genesis_time) / self.period + 2
+    }
+
+    pub fn round_time(&self, round: u64) -> u64 {
+        self.genesis_time + (round - 1) * self.period
+    }
+}
+
+// Key of the game server that signs shot results, so any relayer can submit them
+#[cw_serde]
+pub enum AttestationKey {
+    // 33 byte compressed or 65 byte uncompressed public key
+    Secp256k1(HexBinary),
+    Ed25519(HexBinary),
+}
+
+#[cw_serde]
+pub enum ShotStatus {
+    Pending,
+    Goal,
+    Miss,
+    Expired,
+    // A goal whose payout is held in the dispute window
+    Settling,
+    // A disputed goal rejected by the guardian, the payout went back to the jackpot
+    Voided,
+    // Never resolved, the ticket was paid back to the player
+    Refunded,
+}
+
+impl ShotStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ShotStatus::Pending => "pending",
+            ShotStatus::Goal => "goal",
+            ShotStatus::Miss => "miss",
+            ShotStatus::Expired => "expired",
+            ShotStatus::Settling => "settling",
+            ShotStatus::Voided => "voided",
+            ShotStatus::Refunded => "refunded",
+        }
+    }
+}
+
+// One record per paid `Shoot`, resolved at most once
+#[cw_serde]
+pub struct ShotSession {
+    pub id: u64,
+    pub player: Addr,
+    pub paid_amount: u128,
+    pub started_at: u64,
+    // shoot duration in effect when the shot was taken
+    pub duration: u64,
+    pub deadline: u64,
+    pub status: ShotStatus,
+    // part of the paid amount that went to the admin fee bucket
+    pub ticket_fee: u128,
+    // jackpot the prize is computed from, locked at shoot time and only ever lowered by re-basing
+    pub jackpot_basis: u128,
+    pub fee_schedule: FeeSchedule,
+    pub resolution: ShotResolution,
+}
+
+impl ShotSession {
+    pub fn reward_amount(&self) -> u128 {
+        self.jackpot_basis * self.fee_schedule.reward_bps as u128 / BPS_DENOMINATOR
+    }
+}
+
+// Internal accounting of the funds held by the contract, in `MAIN_DENOM`
+#[cw_serde]
+#[derive(Default)]
+pub struct Ledger {
+    pub jackpot: u128,
+    pub admin_fees: u128,
+    pub reserve: u128,
+    // prizes and escrowed payouts owed to addresses, withdrawn with `Claim`
+    pub claimable: u128,
+    // payouts of goals held in the dispute window
+    pub settling: u128,
+    // operator bond, bonded and unbonding
+    pub bond: u128,
+    // rounding remainders of the admin and reserve shares, in 1/10000 units,
+    // carried forward into the next payout
+    pub admin_dust: u128,
+    pub reserve_dust: u128,
+    // lifetime totals of the admin fee bucket
+    pub fees_accrued: u128,
+    pub fees_withdrawn: u128,
+}
+
+impl Ledger {
+    pub fn total(&self) -> u128 {
+        self.jackpot + self.admin_fees + self.reserve + self.claimable + self.settling
+            + self.bond
+    }
+}
+
+#[cw_serde]
+pub enum PoolBucket {
+    Jackpot,
+    AdminFees,
+    Reserve,
+}
+
+// Goals paying more than `threshold` are held for `period` seconds,
+// during which the guardian can dispute them
+#[cw_serde]
+pub struct DisputeConfig {
+    pub threshold: u128,
+    pub period: u64,
+    pub guardian: Addr,
+}
+
+// Payout of a goal, taken out of the jackpot when the goal is called
+#[cw_serde]
+pub struct Settlement {
+    pub reward_amount: u128,
+    pub admin_amount: u128,
+    pub reserve_amount: u128,
+    pub release_at: u64,
+    pub disputed: bool,
+}
+
+impl Settlement {
+    pub fn total(&self) -> u128 {
+        self.reward_amount + self.admin_amount + self.reserve_amount
+    }
+}
+
+// Bond posted by the owner as operator, part of it is slashed into the jackpot
+// when a resolution is proven fraudulent and it pays the prize of a withheld seed
+#[cw_serde]
+#[derive(Default)]
+pub struct OperatorBond {
+    // address that posted the bond, only it can unbond and withdraw
+    pub operator: Option<Addr>,
+    pub bonded: u128,
+    // part of `bonded` backing the prizes of shots waiting for a seed reveal
+    pub locked: u128,
+    pub unbonding: u128,
+    pub unbonding_release_at: u64,
+    pub total_slashed: u128,
+}
+
+impl OperatorBond {
+    pub fn unlocked(&self) -> u128 {
+        self.bonded.saturating_sub(self.locked)
+    }
+}
+
+#[cw_serde]
+pub struct BondConfig {
+    pub unbonding_period: u64,
+    // share of the whole bond slashed per offence
+    pub slash_bps: u16,
+}
+
+#[cw_serde]
+pub struct SlashRecord {
+    pub shot_id: u64,
+    pub amount: u128,
+    pub reason: String,
+    pub timestamp: u64,
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
+pub const PENDING_OWNER: Item<PendingOwner> = Item::new("pending_owner");
+pub const CONFIG_CHANGE_COUNT: Item<u64> = Item::new("config_change_count");
+pub const PENDING_CONFIG_CHANGES: Map<u64, PendingConfigChange> =
+    Map::new("pending_config_changes");
 pub const MAIN_DENOM: Item<String> = Item::new("main_denom");
+pub const LEDGER: Item<Ledger> = Item::new("ledger");
+pub const CLAIMABLE: Map<&Addr, u128> = Map::new("claimable");
+// Transfers sent as submessages, kept until their reply so failed ones can be escrowed
+pub const PAYOUT_COUNT: Item<u64> = Item::new("payout_count");
+pub const PENDING_PAYOUTS: Map<u64, (Addr, u128)> = Map::new("pending_payouts");
+// Fee burns share the payout ids, a failed one is moved into the reserve
+pub const PENDING_BURNS: Map<u64, u128> = Map::new("pending_burns");
 pub const SHOOT_DEADLINE_MAPPER: Map<Addr, u64> = Map::new("shoot_deadline_mapper");
+pub const LAST_SHOT_MAPPER: Map<Addr, u64> = Map::new("last_shot_mapper");
+pub const SHOT_COUNT: Item<u64> = Item::new("shot_count");
+pub const SHOT_SESSIONS: Map<u64, ShotSession> = Map::new("shot_sessions");
+pub const COMMITMENT_COUNT: Item<u64> = Item::new("commitment_count");
+// id of the next commitment to bind to a commit-reveal shot
+pub const NEXT_COMMITMENT: Item<u64> = Item::new("next_commitment");
+pub const SEED_COMMITMENTS: Map<u64, SeedCommitment> = Map::new("seed_commitments");
+pub const DRAND_CONFIG: Item<DrandConfig> = Item::new("drand_config");
+pub const ATTESTATION_KEY: Item<AttestationKey> = Item::new("attestation_key");
+pub const DISPUTE_CONFIG: Item<DisputeConfig> = Item::new("dispute_config");
+// shot id => payout held in the dispute window
+pub const SETTLEMENTS: Map<u64, Settlement> = Map::new("settlements");
+pub const LIVENESS: Item<Liveness> = Item::new("liveness");
+pub const OPERATOR_BOND: Item<OperatorBond> = Item::new("operator_bond");
+pub const BOND_CONFIG: Item<BondConfig> = Item::new("bond_config");
+pub const SLASH_COUNT: Item<u64> = Item::new("slash_count");
+pub const SLASHES: Map<u64, SlashRecord> = Map::new("slashes");
+pub const RANDOMNESS_PROXY: Item<Addr> = Item::new("randomness_proxy");
+// pause target => engaged switch
+pub const PAUSES: Map<&str, Pause> = Map::new("pauses");
+// (role, address) for every granted role
+pub const ROLES: Map<(&str, &Addr), Empty> = Map::new("roles");
+// goal votes needed to pay out a referee quorum shot
+pub const REFEREE_THRESHOLD: Item<u32> = Item::new("referee_threshold");
+// (shot id, referee) => time of the goal vote
+pub const GOAL_VOTES: Map<(u64, &Addr), u64> = Map::new("goal_votes");
+// shot id => deadline, for every session still in `Pending`
+pub const PENDING_SHOTS: Map<u64, u64> = Map::new("pending_shots");